@@ -0,0 +1,39 @@
+//! Compares a large `CowRc`/`CowArc` with its clone (same allocation) and with a distinct equal value.
+//!
+//! Run with `cargo bench --bench eq`
+
+use optimistic_mutation::prelude::*;
+use std::{
+	hint::black_box,
+	time::{Duration, Instant},
+};
+
+const LEN: usize = 1 << 20;
+const ITERATIONS: u32 = 100;
+
+fn time(name: &str, mut f: impl FnMut() -> bool) {
+	let start = Instant::now();
+	for _ in 0..ITERATIONS {
+		assert!(black_box(f()));
+	}
+	let per_iteration: Duration = start.elapsed() / ITERATIONS;
+	println!("{name:<24} {per_iteration:>12?}");
+}
+
+fn main() {
+	let rc = CowRc::new((0..LEN as u64).collect::<Vec<_>>());
+	let aliased = rc.clone();
+	let distinct = CowRc::new((*rc).clone());
+	time("CowRc aliased", || black_box(&rc) == black_box(&aliased));
+	time("CowRc distinct equal", || {
+		black_box(&rc) == black_box(&distinct)
+	});
+
+	let arc = CowArc::new((0..LEN as u64).collect::<Vec<_>>());
+	let aliased = arc.clone();
+	let distinct = CowArc::new((*arc).clone());
+	time("CowArc aliased", || black_box(&arc) == black_box(&aliased));
+	time("CowArc distinct equal", || {
+		black_box(&arc) == black_box(&distinct)
+	});
+}
@@ -0,0 +1,56 @@
+//! Reports of the clones caused by mutating shared values, enabled by the `debug-alias` feature
+//! in debug builds, to find where unexpected clones happen
+
+#[cfg(test)]
+use std::cell::Cell;
+
+#[cfg(test)]
+thread_local! {
+	static REPORTS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Warns on stderr that mutating a shared `pointer<T>` is about to clone it
+pub fn report_clone<T: ?Sized>(pointer: &str) {
+	if cfg!(debug_assertions) {
+		eprintln!(
+			"warning: mutating a shared {pointer}<{}> clones it",
+			std::any::type_name::<T>(),
+		);
+		#[cfg(test)]
+		REPORTS.set(REPORTS.get() + 1);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{rc::CowRc, sync::CowArc};
+
+	#[test]
+	fn reports_shared_mutation_only() {
+		let mut unique = CowRc::new(vec![1]);
+		unique.push(2);
+		AsMut::<Vec<i32>>::as_mut(&mut unique).push(3);
+		CowRc::with_alias_check(&mut unique, |vec| vec.push(4));
+		assert_eq!(REPORTS.get(), 0);
+
+		let shared = unique.clone();
+		unique.push(5);
+		assert_eq!(REPORTS.get(), 1);
+		drop(shared);
+
+		let mut unique = CowArc::new(String::from("unique"));
+		unique.push('!');
+		assert_eq!(REPORTS.get(), 1);
+
+		let shared = unique.clone();
+		CowArc::with_alias_check(&mut unique, |string| string.push('?'));
+		assert_eq!(REPORTS.get(), 2);
+		assert_eq!((unique.as_str(), shared.as_str()), ("unique!?", "unique!"));
+
+		let mut explicit = shared.clone();
+		CowArc::make_mut(&mut explicit).clear();
+		assert_eq!(REPORTS.get(), 2); // Explicit clones aren't reported
+		assert_eq!((explicit.as_str(), shared.as_str()), ("", "unique!"));
+	}
+}
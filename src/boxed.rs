@@ -0,0 +1,111 @@
+use crate::rc::CowRc;
+use std::{
+	ops::{Deref, DerefMut},
+	rc::Rc,
+};
+use sugaru::pipeline;
+
+/// A value which is definitely unique, until it is [`share`](CowBox::share)d.
+///
+/// Same API shape as [`CowRc`], but backed by a [`Box`]: mutating never checks nor clones.
+/// Sharing moves it into a `CowRc`, copy-on-write from then on
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct CowBox<T: ?Sized> {
+	boxed: Box<T>,
+}
+
+impl<T> CowBox<T> {
+	pub fn new(value: T) -> Self {
+		pipeline!(value |> Box::new |> Self::from_box)
+	}
+
+	#[must_use]
+	pub fn into_inner(this: Self) -> T {
+		*this.boxed
+	}
+}
+
+impl<T: ?Sized> CowBox<T> {
+	#[must_use]
+	pub const fn from_box(boxed: Box<T>) -> Self {
+		Self { boxed }
+	}
+
+	/// Moves the value into a [`CowRc`], which can then be cloned cheaply
+	#[must_use]
+	pub fn share(this: Self) -> CowRc<T> {
+		pipeline!(this.boxed |> Rc::from |> CowRc::from_rc)
+	}
+}
+
+impl<T: ?Sized> Deref for CowBox<T> {
+	type Target = T;
+
+	fn deref(&self) -> &Self::Target {
+		&self.boxed
+	}
+}
+
+impl<T: ?Sized> DerefMut for CowBox<T> {
+	/// Never clones: the value is unique
+	fn deref_mut(&mut self) -> &mut Self::Target {
+		&mut self.boxed
+	}
+}
+
+impl<T: ?Sized> AsRef<T> for CowBox<T> {
+	fn as_ref(&self) -> &T {
+		self
+	}
+}
+
+impl<T: ?Sized> AsMut<T> for CowBox<T> {
+	fn as_mut(&mut self) -> &mut T {
+		self
+	}
+}
+
+impl<T: ?Sized> From<Box<T>> for CowBox<T> {
+	fn from(value: Box<T>) -> Self {
+		Self::from_box(value)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::cell::Cell;
+
+	thread_local! {
+		static CLONES: Cell<usize> = const { Cell::new(0) };
+	}
+
+	/// Counts its clones in `CLONES`
+	#[derive(Debug, PartialEq)]
+	struct Counted(i32);
+
+	impl Clone for Counted {
+		fn clone(&self) -> Self {
+			CLONES.set(CLONES.get() + 1);
+			Self(self.0)
+		}
+	}
+
+	#[test]
+	fn share() {
+		let mut unique = CowBox::new(Counted(1));
+		unique.0 += 1;
+		unique.0 *= 10;
+		assert_eq!(CLONES.get(), 0);
+
+		let mut shared = CowBox::share(unique);
+		let other = shared.clone();
+		assert_eq!(CLONES.get(), 0);
+		CowRc::make_mut(&mut shared).0 += 1; // Copy-on-write
+		assert_eq!(CLONES.get(), 1);
+		assert_eq!((shared.0, other.0), (21, 20));
+
+		let slice: CowBox<[i32]> = CowBox::from(Box::from([1, 2]));
+		assert_eq!(&*CowBox::share(slice), [1, 2]);
+	}
+}
@@ -0,0 +1,93 @@
+use crate::rc::CowRc;
+use std::{fmt, ops::Deref, rc::Rc};
+use sugaru::pipeline;
+
+/// A growable string, which is [`freeze`](CowStrBuilder::freeze)d into a [`CowRc<str>`] once built.
+///
+/// Pushing only reallocates the `String`, never a shared allocation.
+/// Freezing copies the content once, then clones are cheap
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct CowStrBuilder {
+	string: String,
+}
+
+impl CowStrBuilder {
+	#[must_use]
+	pub const fn new() -> Self {
+		Self {
+			string: String::new(),
+		}
+	}
+
+	#[must_use]
+	pub fn with_capacity(capacity: usize) -> Self {
+		pipeline!(capacity |> String::with_capacity |> Self::from)
+	}
+
+	pub fn push_str(&mut self, string: &str) -> &mut Self {
+		self.string.push_str(string);
+		self
+	}
+
+	pub fn push(&mut self, char: char) -> &mut Self {
+		self.string.push(char);
+		self
+	}
+
+	#[must_use]
+	pub fn freeze(self) -> CowRc<str> {
+		pipeline!(self.string |> Rc::from |> CowRc::from_rc)
+	}
+}
+
+impl Deref for CowStrBuilder {
+	type Target = str;
+
+	fn deref(&self) -> &Self::Target {
+		&self.string
+	}
+}
+
+impl AsRef<str> for CowStrBuilder {
+	fn as_ref(&self) -> &str {
+		self
+	}
+}
+
+impl fmt::Write for CowStrBuilder {
+	fn write_str(&mut self, s: &str) -> fmt::Result {
+		self.push_str(s);
+		Ok(())
+	}
+
+	fn write_char(&mut self, c: char) -> fmt::Result {
+		self.push(c);
+		Ok(())
+	}
+}
+
+impl From<String> for CowStrBuilder {
+	fn from(string: String) -> Self {
+		Self { string }
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::fmt::Write;
+
+	#[test]
+	fn freeze() {
+		let mut builder = CowStrBuilder::with_capacity(16);
+		builder.push_str("Hello").push(',').push(' ');
+		let world = "world";
+		write!(builder, "{world}!").unwrap();
+		assert_eq!(&*builder, "Hello, world!");
+
+		let frozen = builder.freeze();
+		let clone = frozen.clone();
+		assert_eq!(&*clone, "Hello, world!");
+		assert!(CowRc::ptr_eq(&frozen, &clone));
+	}
+}
@@ -0,0 +1,90 @@
+use crate::rc::{CowRc, WeakCowRc};
+use std::{borrow::Borrow, cell::RefCell, collections::HashMap, hash::Hash};
+
+/// Map holding its values through weak pointers: it doesn't keep them alive.
+///
+/// Dead entries are pruned when they are looked up, or all at once with [`prune`](Self::prune)
+#[derive(Debug)]
+pub struct WeakValueCache<K, V: ?Sized> {
+	map: RefCell<HashMap<K, WeakCowRc<V>>>,
+}
+
+impl<K: Hash + Eq, V: ?Sized> WeakValueCache<K, V> {
+	#[must_use]
+	pub fn new() -> Self {
+		Self {
+			map: RefCell::default(),
+		}
+	}
+
+	/// Returns the value of `key` if it is still alive, removing the entry otherwise
+	pub fn get<Q>(&self, key: &Q) -> Option<CowRc<V>>
+	where
+		K: Borrow<Q>,
+		Q: ?Sized + Hash + Eq,
+	{
+		let mut map = self.map.borrow_mut();
+		let value = map.get(key)?.upgrade();
+		if value.is_none() {
+			map.remove(key);
+		}
+		value
+	}
+
+	/// Stores a weak pointer to `value`, returning the previous value if it was still alive
+	pub fn insert(&self, key: K, value: &CowRc<V>) -> Option<CowRc<V>> {
+		self.map
+			.borrow_mut()
+			.insert(key, CowRc::downgrade(value))?
+			.upgrade()
+	}
+
+	/// Removes the entries whose value was dropped
+	pub fn prune(&self) {
+		self.map
+			.borrow_mut()
+			.retain(|_, weak| weak.weak.strong_count() > 0);
+	}
+
+	/// Number of entries, including the dead ones not pruned yet
+	#[must_use]
+	pub fn len(&self) -> usize {
+		self.map.borrow().len()
+	}
+
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.map.borrow().is_empty()
+	}
+}
+
+impl<K: Hash + Eq, V: ?Sized> Default for WeakValueCache<K, V> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn get_prunes_dead_entries() {
+		let cache: WeakValueCache<String, str> = WeakValueCache::new();
+		let value: CowRc<str> = CowRc::from("value");
+		let other: CowRc<str> = CowRc::from("other");
+		assert!(cache.insert("key".to_owned(), &value).is_none());
+		cache.insert("other".to_owned(), &other);
+
+		assert_eq!(cache.get("key"), Some(value.clone()));
+		assert_eq!(cache.len(), 2);
+
+		drop(value);
+		assert_eq!(cache.get("key"), None);
+		assert_eq!(cache.len(), 1);
+
+		drop(other);
+		cache.prune();
+		assert!(cache.is_empty());
+	}
+}
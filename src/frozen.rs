@@ -0,0 +1,73 @@
+//! Read-only shared values.
+//!
+//! A [`Frozen`] pointer derefs to its value but never mutably,
+//! so handing one out guarantees at the type level that no clone-on-write happens:
+//!
+//! ```
+//! use optimistic_mutation::prelude::*;
+//!
+//! let frozen = CowRc::freeze(CowRc::new(vec![1, 2]));
+//! assert_eq!(frozen.len(), 2);
+//!
+//! let mut thawed = Frozen::thaw(frozen);
+//! thawed.push(3);
+//! assert_eq!(*thawed, [1, 2, 3]);
+//! ```
+//!
+//! ```compile_fail
+//! use optimistic_mutation::prelude::*;
+//!
+//! let mut frozen = CowRc::freeze(CowRc::new(vec![1, 2]));
+//! frozen.push(3); // No DerefMut
+//! ```
+
+use std::ops::Deref;
+
+/// Wraps a [`CowRc`](crate::rc::CowRc) or a [`CowArc`](crate::sync::CowArc),
+/// implementing [`Deref`] but not `DerefMut`
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct Frozen<C> {
+	pointer: C,
+}
+
+impl<C> Frozen<C> {
+	#[must_use]
+	pub const fn new(pointer: C) -> Self {
+		Self { pointer }
+	}
+
+	/// Gives back the mutable pointer
+	#[must_use]
+	pub fn thaw(this: Self) -> C {
+		this.pointer
+	}
+}
+
+impl<C: Deref> Deref for Frozen<C> {
+	type Target = C::Target;
+
+	fn deref(&self) -> &Self::Target {
+		&self.pointer
+	}
+}
+
+impl<C: Deref> AsRef<C::Target> for Frozen<C> {
+	fn as_ref(&self) -> &C::Target {
+		self
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sync::CowArc;
+
+	#[test]
+	fn freeze() {
+		let arc: CowArc<str> = CowArc::from("shared");
+		let frozen = CowArc::freeze(arc.clone());
+		assert_eq!(&*frozen, "shared");
+		assert_eq!(frozen.len(), 6);
+		assert!(CowArc::ptr_eq(&Frozen::thaw(frozen), &arc)); // Never cloned
+	}
+}
@@ -0,0 +1,96 @@
+use std::{
+	hash::{DefaultHasher, Hash, Hasher},
+	ops::Deref,
+};
+
+/// A value whose hash is computed once, at construction.
+///
+/// For hash keys which are expensive to hash, like a long `CowRc<str>` or `CowRc<[T]>`:
+/// each map probe then hashes a single `u64`, and comparisons check the hashes first.
+/// There is no mutable access, since mutating the value would make the cached hash stale.
+///
+/// The hash is computed with a [`DefaultHasher`] created with fixed keys,
+/// so don't use it for keys chosen by an attacker (see [`RandomState`](std::hash::RandomState))
+#[derive(Debug, Clone)]
+pub struct Hashed<T> {
+	value: T,
+	hash: u64,
+}
+
+impl<T: Hash> Hashed<T> {
+	pub fn new(value: T) -> Self {
+		let mut hasher = DefaultHasher::new();
+		value.hash(&mut hasher);
+		Self {
+			hash: hasher.finish(),
+			value,
+		}
+	}
+}
+
+impl<T> Hashed<T> {
+	#[must_use]
+	pub const fn cached_hash(this: &Self) -> u64 {
+		this.hash
+	}
+
+	#[must_use]
+	pub fn into_inner(this: Self) -> T {
+		this.value
+	}
+}
+
+impl<T> Deref for Hashed<T> {
+	type Target = T;
+
+	fn deref(&self) -> &Self::Target {
+		&self.value
+	}
+}
+
+impl<T: Hash> From<T> for Hashed<T> {
+	fn from(value: T) -> Self {
+		Self::new(value)
+	}
+}
+
+impl<T: Eq> PartialEq for Hashed<T> {
+	/// Compares the cached hashes before the values
+	fn eq(&self, other: &Self) -> bool {
+		self.hash == other.hash && self.value == other.value
+	}
+}
+
+impl<T: Eq> Eq for Hashed<T> {}
+
+impl<T> Hash for Hashed<T> {
+	/// Writes the cached hash only: unlike the `CowRc` pointers, a `Hashed<T>` doesn't hash
+	/// like its `T`, which is why it doesn't implement [`Borrow<T>`](std::borrow::Borrow)
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		state.write_u64(self.hash);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::rc::CowRc;
+	use std::collections::HashMap;
+
+	#[test]
+	fn cached_hash() {
+		let text: CowRc<str> = CowRc::from("a long document".repeat(100).as_str());
+		let hashed = Hashed::new(text.clone());
+		let mut fresh = DefaultHasher::new();
+		text.hash(&mut fresh);
+		assert_eq!(Hashed::cached_hash(&hashed), fresh.finish());
+
+		let mut counts = HashMap::new();
+		counts.insert(hashed, 1);
+		counts.insert(Hashed::new(CowRc::from("other")), 2);
+		*counts.get_mut(&Hashed::new(text)).unwrap() += 1;
+		assert_eq!(counts.len(), 2);
+		assert_eq!(counts.get(&Hashed::from(CowRc::from("other"))), Some(&2));
+		assert_eq!(counts.values().sum::<i32>(), 4);
+	}
+}
@@ -0,0 +1,192 @@
+use crate::rc::CowRc;
+use std::{
+	collections::{HashMap, HashSet},
+	hash::{BuildHasher, Hash},
+};
+
+/// Deduplicates strings: equal strings share the same allocation
+#[derive(Debug, Default)]
+pub struct StrInterner {
+	strings: HashSet<CowRc<str>>,
+}
+
+impl StrInterner {
+	#[must_use]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Returns the shared string equal to `string`, allocating it only the first time
+	pub fn intern(&mut self, string: &str) -> CowRc<str> {
+		if let Some(interned) = self.strings.get(string) {
+			return interned.clone();
+		}
+		let interned: CowRc<str> = CowRc::from(string);
+		self.strings.insert(interned.clone());
+		interned
+	}
+
+	#[must_use]
+	pub fn len(&self) -> usize {
+		self.strings.len()
+	}
+
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.strings.is_empty()
+	}
+}
+
+/// Deduplicates slices, like [`StrInterner`] for byte sequences or token ids
+#[derive(Debug)]
+pub struct SliceInterner<T> {
+	slices: HashSet<CowRc<[T]>>,
+}
+
+impl<T: Hash + Eq + Clone> SliceInterner<T> {
+	#[must_use]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Returns the shared slice equal to `slice`, allocating it only the first time
+	pub fn intern(&mut self, slice: &[T]) -> CowRc<[T]> {
+		if let Some(interned) = self.slices.get(slice) {
+			return interned.clone();
+		}
+		let interned: CowRc<[T]> = CowRc::from(slice);
+		self.slices.insert(interned.clone());
+		interned
+	}
+
+	#[must_use]
+	pub fn len(&self) -> usize {
+		self.slices.len()
+	}
+
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.slices.is_empty()
+	}
+}
+
+impl<T> Default for SliceInterner<T> {
+	fn default() -> Self {
+		Self {
+			slices: HashSet::new(),
+		}
+	}
+}
+
+/// Gets the value of `key` in `map`, inserting `V::default()` if the key is missing.
+///
+/// Unlike [`HashMap::entry`], the `CowRc<str>` key is only allocated on a miss:
+/// a hit is looked up by `&str` through the [`Borrow<str>`](std::borrow::Borrow) implementation
+#[allow(clippy::missing_panics_doc)] // The key is always found before calling get_mut
+pub fn intern_entry<'m, V: Default, S: BuildHasher>(
+	map: &'m mut HashMap<CowRc<str>, V, S>,
+	key: &str,
+) -> &'m mut V {
+	if map.contains_key(key) {
+		// The borrow checker doesn't allow returning a get_mut from an if let
+		// and inserting in the else branch
+		map.get_mut(key).expect("the key was just found")
+	} else {
+		map.entry(CowRc::from(key)).or_default()
+	}
+}
+
+/// Gets the value of `key` in `map` without allocating a `CowRc<[T]>`,
+/// through the [`Borrow<[T]>`](std::borrow::Borrow) implementation
+/// (`map.get(key)` works too, this spells out the intended query)
+pub fn get_by_slice<'m, T: Hash + Eq, V, S: BuildHasher>(
+	map: &'m HashMap<CowRc<[T]>, V, S>,
+	key: &[T],
+) -> Option<&'m V> {
+	map.get(key)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::to_owned::cow_rc_slice::ToCowRcSlice;
+	use std::{
+		alloc::{GlobalAlloc, Layout, System},
+		cell::Cell,
+	};
+
+	thread_local! {
+		static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+	}
+
+	/// Counts the allocations of each thread in `ALLOCATIONS`, so that parallel tests don't interfere
+	struct CountingAllocator;
+
+	unsafe impl GlobalAlloc for CountingAllocator {
+		unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+			ALLOCATIONS.set(ALLOCATIONS.get() + 1);
+			unsafe { System.alloc(layout) }
+		}
+
+		unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+			unsafe { System.dealloc(ptr, layout) }
+		}
+	}
+
+	#[global_allocator]
+	static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+	#[test]
+	fn intern_entry() {
+		let mut counts: HashMap<CowRc<str>, usize> = HashMap::with_capacity(2);
+
+		let before = ALLOCATIONS.get();
+		*super::intern_entry(&mut counts, "a") += 1; // Miss
+		assert_eq!(ALLOCATIONS.get(), before + 1); // The key
+
+		let before = ALLOCATIONS.get();
+		*super::intern_entry(&mut counts, "a") += 1; // Hit
+		assert_eq!(ALLOCATIONS.get(), before); // No allocation on hit
+
+		*super::intern_entry(&mut counts, "b") += 1; // Miss
+		assert_eq!(counts.len(), 2);
+		assert_eq!(counts["a"], 2);
+		assert_eq!(counts["b"], 1);
+	}
+
+	#[test]
+	fn intern_str() {
+		let mut interner = StrInterner::new();
+		let hello = interner.intern("hello");
+		let world = interner.intern("world");
+		let hello_again = interner.intern(&String::from("hello"));
+
+		assert!(CowRc::ptr_eq(&hello, &hello_again));
+		assert!(!CowRc::ptr_eq(&hello, &world));
+		assert_eq!(interner.len(), 2);
+	}
+
+	#[test]
+	fn intern_slice() {
+		let mut interner = SliceInterner::new();
+		let tokens = interner.intern(&[1, 2, 3]);
+		let other = interner.intern(&[3, 2, 1]);
+		let tokens_again = interner.intern(&Vec::from([1, 2, 3]));
+
+		assert!(CowRc::ptr_eq(&tokens, &tokens_again));
+		assert!(!CowRc::ptr_eq(&tokens, &other));
+		assert_eq!(interner.len(), 2);
+	}
+
+	#[test]
+	fn get_by_slice() {
+		let mut sizes: HashMap<CowRc<[u8]>, usize> = HashMap::new();
+		sizes.insert(CowRc::from(&b"GET"[..]), 3);
+		sizes.insert(CowRc::from(&b"POST"[..]), 4);
+
+		let request = b"POST /index.html";
+		assert_eq!(super::get_by_slice(&sizes, &request[..4]), Some(&4));
+		assert_eq!(super::get_by_slice(&sizes, b"PUT"), None);
+		assert_eq!(sizes.get(ToCowRcSlice::from_slice(b"GET")), Some(&3));
+	}
+}
@@ -1,5 +1,21 @@
 // Inspired by: https://www.roc-lang.org/functional#opportunistic-mutation
+#![cfg_attr(feature = "nightly", feature(allocator_api))]
 
+#[cfg(feature = "debug-alias")]
+mod alias;
+pub mod boxed;
+pub mod builder;
+pub mod cache;
+pub mod frozen;
+pub mod hashed;
+pub mod intern;
+mod macros;
+pub mod map;
+pub mod prelude;
 pub mod rc;
+#[cfg(feature = "serde")]
+pub mod serde;
+pub mod sharing;
 pub mod sync;
 mod to_owned;
+pub mod vec;
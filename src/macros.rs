@@ -0,0 +1,74 @@
+/// Implements [`Debug`](std::fmt::Debug) for a struct, printing the fields marked with `*`
+/// through their `Deref` (so a `CowRc<T>` field prints like its `T`) and the other ones as is.
+///
+/// ```
+/// use optimistic_mutation::{cow_debug, prelude::*};
+///
+/// struct Person {
+///     name: CowRc<str>,
+///     age: u8,
+/// }
+///
+/// cow_debug!(Person { *name, age });
+///
+/// let person = Person { name: CowRc::from("Ann"), age: 30 };
+/// assert_eq!(format!("{person:?}"), r#"Person { name: "Ann", age: 30 }"#);
+/// ```
+#[macro_export]
+macro_rules! cow_debug {
+	($Type:ident { $($fields:tt)* }) => {
+		impl ::std::fmt::Debug for $Type {
+			fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+				let mut debug = f.debug_struct(stringify!($Type));
+				$crate::cow_debug!(@fields self debug $($fields)*);
+				debug.finish()
+			}
+		}
+	};
+	(@fields $self:ident $debug:ident) => {};
+	(@fields $self:ident $debug:ident * $field:ident $(, $($rest:tt)*)?) => {
+		$debug.field(stringify!($field), &&*$self.$field);
+		$crate::cow_debug!(@fields $self $debug $($($rest)*)?);
+	};
+	(@fields $self:ident $debug:ident $field:ident $(, $($rest:tt)*)?) => {
+		$debug.field(stringify!($field), &$self.$field);
+		$crate::cow_debug!(@fields $self $debug $($($rest)*)?);
+	};
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{rc::CowRc, sync::CowArc};
+
+	struct Account {
+		owner: CowRc<str>,
+		id: u32,
+		history: CowArc<Vec<i32>>,
+		raw: CowRc<i32>,
+	}
+
+	cow_debug!(Account {
+		*owner,
+		id,
+		*history,
+		raw,
+	});
+
+	#[test]
+	fn cow_debug() {
+		let account = Account {
+			owner: CowRc::from("Ann"),
+			id: 7,
+			history: CowArc::new(vec![1, -2]),
+			raw: CowRc::new(3),
+		};
+		assert_eq!(
+			format!("{account:?}"),
+			format!(
+				"Account {{ owner: \"Ann\", id: 7, history: [1, -2], raw: {:?} }}",
+				account.raw
+			)
+		);
+		assert!(format!("{account:#?}").contains("\n    owner: \"Ann\",\n"));
+	}
+}
@@ -0,0 +1,174 @@
+use crate::sync::CowArc;
+use std::{borrow::Borrow, collections::HashMap, hash::Hash, ops::Deref};
+use sugaru::pipeline;
+
+/// Thread-safe copy-on-write hash map, wrapping a `CowArc<HashMap<K, V>>`.
+///
+/// Cloning it takes a snapshot without copying the entries.
+/// The first insertion or removal after sharing clones the whole map,
+/// the following ones mutate it in place until it is shared again
+#[derive(Debug)]
+#[allow(clippy::module_name_repetitions)]
+pub struct CowArcMap<K, V> {
+	map: CowArc<HashMap<K, V>>,
+}
+
+impl<K, V> CowArcMap<K, V> {
+	#[must_use]
+	pub fn new() -> Self {
+		pipeline!(HashMap::new() => Self::from)
+	}
+
+	#[must_use]
+	pub fn with_capacity(capacity: usize) -> Self {
+		pipeline!(capacity |> HashMap::with_capacity |> Self::from)
+	}
+
+	#[must_use]
+	pub fn len(&self) -> usize {
+		self.map.len()
+	}
+
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.map.is_empty()
+	}
+}
+
+impl<K: Eq + Hash, V> CowArcMap<K, V> {
+	/// Reads a value without cloning anything, even if the map is shared
+	pub fn get<Q>(&self, key: &Q) -> Option<&V>
+	where
+		K: Borrow<Q>,
+		Q: ?Sized + Eq + Hash,
+	{
+		self.map.get(key)
+	}
+}
+
+impl<K: Clone + Eq + Hash, V: Clone> CowArcMap<K, V> {
+	/// Clones the inner `HashMap` only if it is shared
+	fn make_mut(&mut self) -> &mut HashMap<K, V> {
+		CowArc::make_mut(&mut self.map)
+	}
+
+	/// See [`HashMap::insert`], clones the map first if it is shared
+	pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+		self.make_mut().insert(key, value)
+	}
+
+	/// See [`HashMap::remove`], clones the map first if it is shared and contains `key`
+	pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+	where
+		K: Borrow<Q>,
+		Q: ?Sized + Eq + Hash,
+	{
+		if self.map.contains_key(key) {
+			self.make_mut().remove(key)
+		} else {
+			None
+		}
+	}
+}
+
+impl<K, V> Clone for CowArcMap<K, V> {
+	/// Shares the inner `HashMap`, without cloning it
+	fn clone(&self) -> Self {
+		Self {
+			map: self.map.clone(),
+		}
+	}
+}
+
+impl<K: Eq + Hash, V: PartialEq> PartialEq for CowArcMap<K, V> {
+	fn eq(&self, other: &Self) -> bool {
+		self.map == other.map
+	}
+}
+
+impl<K: Eq + Hash, V: Eq> Eq for CowArcMap<K, V> {}
+
+impl<K, V> Default for CowArcMap<K, V> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<K, V> Deref for CowArcMap<K, V> {
+	type Target = HashMap<K, V>;
+
+	fn deref(&self) -> &Self::Target {
+		&self.map
+	}
+}
+
+impl<K, V> From<HashMap<K, V>> for CowArcMap<K, V> {
+	fn from(value: HashMap<K, V>) -> Self {
+		Self {
+			map: CowArc::new(value),
+		}
+	}
+}
+
+/// Builds a map inline, like [`HashMap::from`]. A key repeated in the array keeps its last value
+impl<K: Eq + Hash, V, const N: usize> From<[(K, V); N]> for CowArcMap<K, V> {
+	fn from(value: [(K, V); N]) -> Self {
+		pipeline!(value |> HashMap::from |> Self::from)
+	}
+}
+
+impl<K: Eq + Hash, V> FromIterator<(K, V)> for CowArcMap<K, V> {
+	fn from_iter<Iterator: IntoIterator<Item = (K, V)>>(iter: Iterator) -> Self {
+		pipeline!(iter |> HashMap::from_iter |> Self::from)
+	}
+}
+
+impl<K: Clone + Eq + Hash, V: Clone> Extend<(K, V)> for CowArcMap<K, V> {
+	fn extend<Iterator: IntoIterator<Item = (K, V)>>(&mut self, iter: Iterator) {
+		self.make_mut().extend(iter);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn snapshot() {
+		let mut config: CowArcMap<String, u32> = CowArcMap::new();
+		config.insert("retries".to_owned(), 3);
+
+		let snapshot = config.clone();
+		assert_eq!(config.insert("retries".to_owned(), 5), Some(3));
+		config.extend([("timeout".to_owned(), 30)]);
+		assert_eq!(config.get("retries"), Some(&5));
+		assert_eq!(config.len(), 2);
+
+		assert_eq!(snapshot.get("retries"), Some(&3));
+		assert!(!snapshot.contains_key("timeout"));
+	}
+
+	#[test]
+	fn remove() {
+		let mut map: CowArcMap<i32, i32> = [(1, 10), (2, 20)].into_iter().collect();
+		let snapshot = map.clone();
+		assert_eq!(map.remove(&3), None);
+		assert!(CowArc::ptr_eq(&map.map, &snapshot.map)); // Nothing to remove, still shared
+
+		assert_eq!(map.remove(&1), Some(10));
+		assert_eq!(map.len(), 1);
+		assert_eq!(snapshot.len(), 2);
+
+		let reader = std::thread::spawn(move || snapshot.get(&1).copied());
+		assert_eq!(reader.join().unwrap(), Some(10));
+	}
+
+	#[test]
+	fn from_array() {
+		let map = CowArcMap::from([("a", 1), ("b", 2), ("a", 3)]);
+		assert_eq!(map.len(), 2);
+		assert_eq!(map.get("a"), Some(&3));
+		assert_eq!(map.get("b"), Some(&2));
+		assert_eq!(map.get("c"), None);
+	}
+}
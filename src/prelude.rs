@@ -0,0 +1,32 @@
+//! Re-exports the types needed in most uses of this crate
+//!
+//! ```
+//! use optimistic_mutation::prelude::*;
+//! use std::borrow::Cow;
+//!
+//! let mut rc = CowRc::new(1);
+//! let weak_rc: WeakCowRc<i32> = CowRc::downgrade(&rc);
+//! *rc += 1;
+//! assert!(weak_rc.upgrade().is_none());
+//!
+//! let arc = CowArc::new(1);
+//! let weak_arc: WeakCowArc<i32> = CowArc::downgrade(&arc);
+//! assert_eq!(WeakCowArc::upgrade(&weak_arc), Some(arc));
+//!
+//! let str: Cow<'_, ToCowRcStr> = ToCowRcStr::from_str("Hello").into();
+//! let slice: Cow<'_, ToCowRcSlice<i32>> = ToCowRcSlice::from_slice(&[1, 2]).into();
+//! let array: Cow<'_, ToCowRcArray<i32, 2>> = ToCowRcArray::from_array(&[1, 2]).into();
+//! assert_eq!(str.len() + slice.len() + array.len(), 9);
+//! ```
+
+pub use crate::{
+	boxed::CowBox,
+	builder::CowStrBuilder,
+	frozen::Frozen,
+	map::CowArcMap,
+	rc::{CowRc, ToCowRc, WeakCowRc},
+	sharing::Sharing,
+	sync::{CowArc, ToCowArc, WeakCowArc},
+	to_owned::{cow_rc_array::ToCowRcArray, cow_rc_slice::ToCowRcSlice, cow_rc_str::ToCowRcStr},
+	vec::{CowArcVec, CowRcVec},
+};
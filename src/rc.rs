@@ -1,24 +1,45 @@
 use std::{
-	fmt::Debug,
-	ops::{Deref, DerefMut},
+	borrow::{Borrow, BorrowMut},
+	cmp::Ordering,
+	collections::HashSet,
+	fmt::{Debug, Display, Formatter},
+	hash::{Hash, Hasher},
+	io, mem,
+	ops::{Add, AddAssign, Deref, DerefMut, Div, Mul, Rem, Sub},
 	rc::{Rc, Weak},
 };
 
+use crate::{frozen::Frozen, sharing::Sharing};
 use sugaru::pipeline;
 
-#[derive(Debug, Clone, Eq, PartialEq, Hash, Ord, PartialOrd, Default)]
+#[cfg(feature = "nightly")]
+use std::alloc::AllocError;
+
+/// A copy-on-write reference-counted pointer: mutating a shared `CowRc` clones its value first.
+///
+/// Comparing two `CowRc`s pointing to the same allocation returns true without comparing the values
+/// when `T: Eq` (as [`Rc`] does), which is cheap for large values compared to their clones.
+/// Without `Eq`, values are always compared, since a value may differ from itself (NaN)
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Default)]
 #[allow(clippy::module_name_repetitions)]
 pub struct CowRc<T: ?Sized> {
 	// Private to avoid name collision with a T containing a field named rc
 	rc: Rc<T>,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Default)]
 #[allow(clippy::module_name_repetitions)]
 pub struct WeakCowRc<T: ?Sized> {
 	pub weak: Weak<T>,
 }
 
+/// Mutation guard returned by [`CowRc::scoped_mut`]
+#[derive(Debug)]
+pub struct ScopedMut<'a, T: Clone> {
+	cow: &'a mut CowRc<T>,
+	cloned: bool,
+}
+
 impl<T> CowRc<T> {
 	/// Constructs a new `CowRc<T>`.
 	///
@@ -32,6 +53,101 @@ impl<T> CowRc<T> {
 	pub fn new(value: T) -> Self {
 		pipeline!(value |> Rc::new |> Self::from_rc)
 	}
+
+	/// Constructs a new `CowRc<T>`, returning an error if the allocation fails
+	///
+	/// # Errors
+	///
+	/// Returns [`AllocError`] if the allocation fails
+	#[cfg(feature = "nightly")]
+	pub fn try_new(value: T) -> Result<Self, AllocError> {
+		Rc::try_new(value).map(Self::from_rc)
+	}
+
+	/// Points this `CowRc` to a new allocation of `value`, returning the previous pointer
+	/// (not the previous value, which would have to be cloned if shared)
+	#[must_use = "if the previous value is unused, assign a new `CowRc` instead"]
+	pub fn replace(this: &mut Self, value: T) -> Self {
+		mem::replace(this, Self::new(value))
+	}
+
+	/// Rewrites a node of a persistent structure: returns `root` itself, shared, if `f` leaves
+	/// it unchanged (`None`), a new node otherwise. Calling it recursively from `f` on the
+	/// children keeps every unchanged subtree shared with the original structure
+	#[must_use]
+	pub fn rewrite(root: &Self, f: impl Fn(&T) -> Option<T>) -> Self {
+		f(root).map_or_else(|| root.clone(), Self::new)
+	}
+}
+
+impl<T: Clone> CowRc<T> {
+	/// Same as [`CowRc::unwrap_or_clone`]. Replaces `Rc::into_inner(..).unwrap_or_default()`,
+	/// which builds a default value and loses the shared one when this `CowRc` isn't unique
+	#[must_use]
+	pub fn unwrap_or_default(this: Self) -> T
+	where
+		T: Default,
+	{
+		Self::unwrap_or_clone(this)
+	}
+
+	/// Returns a guard which clones the value, if this `CowRc` is shared, only when first dereferenced mutably.
+	/// Reading through it never clones, and [`ScopedMut::cloned`] tells whether writing did
+	pub const fn scoped_mut(this: &mut Self) -> ScopedMut<'_, T> {
+		ScopedMut {
+			cow: this,
+			cloned: false,
+		}
+	}
+
+	/// Overwrites `dest` with the value of `this`. If `dest` is unique, the value is cloned into its allocation
+	/// with [`Clone::clone_from`] (reusing the allocations of the value too, e.g. a `Vec` buffer),
+	/// instead of freeing it. Otherwise `dest` shares the value of `this`, like assigning a clone of the `CowRc`
+	pub fn assign_into(this: &Self, dest: &mut Self) {
+		match Rc::get_mut(&mut dest.rc) {
+			Some(value) => value.clone_from(this),
+			None => *dest = this.clone(),
+		}
+	}
+
+	/// Returns a unique `CowRc` with a clone of the value, detached from this one.
+	/// Unlike [`Clone::clone`], which shares the value, this always clones it
+	#[must_use]
+	pub fn fork(this: &Self) -> Self {
+		pipeline!(T::clone(this) |> Self::new)
+	}
+
+	/// Returns the inner value, moving it out if this `CowRc` is the only strong pointer,
+	/// cloning it otherwise. See [`Rc::unwrap_or_clone`]
+	#[must_use]
+	pub fn unwrap_or_clone(this: Self) -> T {
+		pipeline!(this.rc => Rc::unwrap_or_clone)
+	}
+
+	/// Applies a fallible transformation to the inner value,
+	/// moved out if this `CowRc` is the only strong pointer, cloned otherwise
+	///
+	/// # Errors
+	///
+	/// Returns the error of `f`
+	pub fn try_map<U, E>(this: Self, f: impl FnOnce(T) -> Result<U, E>) -> Result<CowRc<U>, E> {
+		f(Self::unwrap_or_clone(this)).map(CowRc::new)
+	}
+
+	/// Applies an in-place edit, cloning the value first if this `CowRc` is shared,
+	/// and returns `self` to chain edits (only the first one may clone).
+	/// A method rather than an associated function, for chaining
+	pub fn modify(&mut self, f: impl FnOnce(&mut T)) -> &mut Self {
+		f(Self::make_mut(self));
+		self
+	}
+
+	/// Swaps the values pointed to by `a` and `b`, cloning them first if they are shared.
+	///
+	/// Unlike [`CowRc::swap`], unique values are swapped in place, keeping their addresses
+	pub fn swap_contents(a: &mut Self, b: &mut Self) {
+		mem::swap(Self::make_mut(a), Self::make_mut(b));
+	}
 }
 
 #[allow(clippy::wrong_self_convention)] // CowRc est un smart pointer et il faut éviter les méthodes qui ont des noms
@@ -52,6 +168,7 @@ impl<T: ?Sized> CowRc<T> {
 		Self { rc }
 	}
 
+	/// Borrows the wrapped [`Rc`], to feed APIs taking a `&Rc<T>` without moving nor cloning it
 	#[must_use]
 	pub const fn as_rc(this: &Self) -> &Rc<T> {
 		&this.rc
@@ -82,12 +199,203 @@ impl<T: ?Sized> CowRc<T> {
 		!Self::needs_cloning_to_mutate(this) && Rc::weak_count(&this.rc) == 0
 	}
 
+	/// Returns true if both `CowRc` point to the same allocation, see [`Rc::ptr_eq`]
+	#[inline]
+	#[must_use]
+	pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+		Rc::ptr_eq(&this.rc, &other.rc)
+	}
+
+	/// Orders by allocation address, a total order on identities which doesn't need `T: Ord`
+	/// (e.g. to key a `BTreeMap` on graph nodes). Consistent with [`CowRc::ptr_eq`]
+	#[must_use]
+	pub fn cmp_by_ptr(this: &Self, other: &Self) -> Ordering {
+		Rc::as_ptr(&this.rc)
+			.cast::<()>()
+			.cmp(&Rc::as_ptr(&other.rc).cast())
+	}
+
+	/// Classifies the sharing state of this `CowRc`, combining
+	/// [`needs_cloning_to_mutate`](Self::needs_cloning_to_mutate) and [`is_unique`](Self::is_unique)
+	#[must_use]
+	pub fn sharing(this: &Self) -> Sharing {
+		if Self::needs_cloning_to_mutate(this) {
+			Sharing::Shared
+		} else if Self::is_unique(this) {
+			Sharing::Unique
+		} else {
+			Sharing::Observed
+		}
+	}
+
+	/// Panics in debug builds if this `CowRc` is not unique.
+	///
+	/// Put it before a batch of mutations expected not to clone, to catch accidental sharing
+	#[inline]
+	#[track_caller]
+	pub fn assert_unique(this: &Self) {
+		debug_assert!(
+			Self::is_unique(this),
+			"CowRc<{}> is not unique: mutating it would clone (strong count: {}, weak count: {})",
+			std::any::type_name::<T>(),
+			Rc::strong_count(&this.rc),
+			Rc::weak_count(&this.rc),
+		);
+	}
+
+	/// Swaps the pointers of `a` and `b`, without cloning nor moving the values
+	/// (same as [`mem::swap`] on the `CowRc`s)
+	pub const fn swap(a: &mut Self, b: &mut Self) {
+		mem::swap(&mut a.rc, &mut b.rc);
+	}
+
+	/// Makes this `CowRc` read-only, see [`Frozen`]
+	#[must_use]
+	pub const fn freeze(this: Self) -> Frozen<Self> {
+		Frozen::new(this)
+	}
+
+	/// Runs `f` on the inner value and returns this `CowRc`, like [`Iterator::inspect`]
+	#[must_use]
+	pub fn inspect(this: Self, f: impl FnOnce(&T)) -> Self {
+		f(&this);
+		this
+	}
+
+	/// Reads the inner value through `f`
+	pub fn map_ref<R>(this: &Self, f: impl FnOnce(&T) -> R) -> R {
+		f(this)
+	}
+
+	/// Mutably borrows the inner value, cloning it first if this `CowRc` is shared.
+	/// Same as dereferencing mutably, but never checked by the `strict-deref-mut` feature
+	pub fn make_mut(this: &mut Self) -> &mut T
+	where
+		T: Clone,
+	{
+		pipeline!(&mut this.rc => Rc::make_mut)
+	}
+
+	/// Mutably borrows the inner value for the duration of `f`, cloning it first if this `CowRc` is shared.
+	/// With the `debug-alias` feature, debug builds report that clone on stderr
+	pub fn with_alias_check<R>(this: &mut Self, f: impl FnOnce(&mut T) -> R) -> R
+	where
+		T: Clone,
+	{
+		#[cfg(feature = "debug-alias")]
+		Self::report_alias(this);
+		f(Self::make_mut(this))
+	}
+
+	#[cfg(feature = "debug-alias")]
+	fn report_alias(this: &Self) {
+		if Self::needs_cloning_to_mutate(this) {
+			crate::alias::report_clone::<T>("CowRc");
+		}
+	}
+
 	#[must_use = "this returns a new `Weak` pointer, without modifying the original `CowRc`"]
 	pub fn downgrade(this: &Self) -> WeakCowRc<T> {
 		pipeline!(&this.rc => Rc::downgrade => WeakCowRc::from_weak)
 	}
+
+	/// Consumes the `CowRc` and returns a reference with a `'static` lifetime to its value.
+	///
+	/// The strong count is never decremented, so the value is intentionally leaked:
+	/// it is never dropped and the allocation lives until the end of the program.
+	/// Useful for immutable global tables built once at startup
+	#[must_use]
+	pub fn leak(this: Self) -> &'static T
+	where
+		T: 'static,
+	{
+		unsafe { &*Rc::into_raw(this.rc) }
+	}
+
+	/// Returns true if `root` can reach itself by following the weak `edges` of each value.
+	///
+	/// Diagnostic helper for graphs whose back-references were meant to be weak:
+	/// dropped targets are skipped, and every allocation is visited once
+	#[must_use]
+	pub fn detect_cycle(root: &Self, edges: impl Fn(&T) -> Vec<WeakCowRc<T>>) -> bool {
+		let mut visited = HashSet::new();
+		let mut stack = edges(root);
+		while let Some(weak) = stack.pop() {
+			let Some(node) = weak.upgrade() else { continue };
+			if Self::ptr_eq(&node, root) {
+				return true;
+			}
+			if visited.insert(Rc::as_ptr(&node.rc).cast::<()>()) {
+				stack.extend(edges(&node));
+			}
+		}
+		false
+	}
 }
 
+impl<T: Clone> CowRc<Vec<T>> {
+	/// Makes a mutable reference into the given `CowRc<Vec<T>>`,
+	/// with room for at least `additional` more elements.
+	///
+	/// Unlike dereferencing then calling [`Vec::reserve`], when the `Vec` has to be cloned,
+	/// the clone is directly allocated with the required capacity (only one allocation)
+	pub fn make_mut_with_capacity(this: &mut Self, additional: usize) -> &mut Vec<T> {
+		if Self::needs_cloning_to_mutate(this) {
+			let mut vec = Vec::with_capacity(this.len() + additional);
+			vec.extend_from_slice(this);
+			*this = Self::new(vec);
+		}
+		let vec = pipeline!(&mut this.rc => Rc::make_mut);
+		vec.reserve(additional);
+		vec
+	}
+
+	/// Adds `scalar` to every element of the `Vec`, cloning it first if it is shared
+	/// (the clone happens once, not per element)
+	#[allow(clippy::needless_pass_by_value)]
+	pub fn add_scalar(this: &mut Self, scalar: T)
+	where
+		T: AddAssign,
+	{
+		for element in Self::make_mut(this) {
+			*element += scalar.clone();
+		}
+	}
+}
+
+impl<T: Clone> CowRc<Option<T>> {
+	/// Returns the value if present, in its own `CowRc`.
+	/// The value is cloned: an `Rc` cannot point inside another allocation
+	#[must_use]
+	pub fn map_option(this: &Self) -> Option<CowRc<T>> {
+		Option::as_ref(this).map(|value| CowRc::new(value.clone()))
+	}
+}
+
+impl<T: ?Sized> Clone for CowRc<T> {
+	/// Makes a clone of the `CowRc` pointer, without cloning the inner value
+	/// (derived `Clone` would require `T: Clone`, excluding `str` and `[T]`)
+	fn clone(&self) -> Self {
+		pipeline!(&self.rc => Rc::clone => Self::from_rc)
+	}
+}
+
+/// Forwards a binary operator to the inner values.
+/// Operands are moved out when unique and only cloned when shared
+macro_rules! forward_binary_operator {
+	($($Operator:ident::$operation:ident),+) => {$(
+		impl<T: Clone + $Operator<Output = T>> $Operator for CowRc<T> {
+			type Output = Self;
+
+			fn $operation(self, rhs: Self) -> Self::Output {
+				pipeline!(Self::unwrap_or_clone(self).$operation(Self::unwrap_or_clone(rhs)) => Self::new)
+			}
+		}
+	)+};
+}
+
+forward_binary_operator!(Add::add, Sub::sub, Mul::mul, Div::div, Rem::rem);
+
 impl<T, U> From<T> for CowRc<U>
 where
 	U: ?Sized,
@@ -114,6 +422,33 @@ where
 	}
 }
 
+// `From<CowRc<T>> for Rc<T>` would make `Rc<U>: From<CowRc<U>>` hold,
+// so the generic `From` above would overlap with the reflexive `From<T> for T`
+#[allow(clippy::from_over_into)]
+impl<T: ?Sized> Into<Rc<T>> for CowRc<T> {
+	/// Moves the wrapped `Rc` out, without touching the reference counts
+	fn into(self) -> Rc<T> {
+		self.rc
+	}
+}
+
+impl<T: ?Sized + Hash> Hash for CowRc<T> {
+	/// Hashes the inner value, so a `CowRc<T>` hashes like its `T`
+	/// (required for lookups through [`Borrow<T>`]).
+	/// `T` may be a trait object, given an `impl Hash for dyn Trait`
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		(**self).hash(state);
+	}
+}
+
+impl<T: ?Sized> From<&Self> for CowRc<T> {
+	/// Clones the `CowRc` pointer (without cloning the inner value),
+	/// for generic code bounded on `From<&CowRc<T>>`
+	fn from(value: &Self) -> Self {
+		value.clone()
+	}
+}
+
 impl<T: ?Sized> Deref for CowRc<T> {
 	type Target = T;
 
@@ -136,9 +471,13 @@ impl<T: ?Sized + Clone> DerefMut for CowRc<T> {
 	/// then any existing [`WeakCowRc`] pointers will be disassociated and the inner value will not be cloned.
 	/// This is referred to as optimistic mutation.
 	///
+	/// With the `strict-deref-mut` feature, debug builds panic instead of cloning,
+	/// so the examples below which clone don't run: use [`CowRc::make_mut`] to clone explicitly
+	///
 	/// # Examples
 	///
-	/// ```
+	#[cfg_attr(not(feature = "strict-deref-mut"), doc = "```")]
+	#[cfg_attr(feature = "strict-deref-mut", doc = "```ignore")]
 	/// use optimistic_mutation::rc::CowRc;
 	///
 	/// let mut data = CowRc::new(5);
@@ -174,7 +513,8 @@ impl<T: ?Sized + Clone> DerefMut for CowRc<T> {
 	///
 	/// However, if there are other strong references to prevent the strong count dropping to 0,
 	/// cloning occurs and [`WeakCowRc`] pointers will continue to point to the old data
-	/// ```
+	#[cfg_attr(not(feature = "strict-deref-mut"), doc = "```")]
+	#[cfg_attr(feature = "strict-deref-mut", doc = "```ignore")]
 	/// use optimistic_mutation::rc::CowRc;
 	///
 	/// let mut data = CowRc::new(75);
@@ -191,7 +531,15 @@ impl<T: ?Sized + Clone> DerefMut for CowRc<T> {
 	/// assert!(weak.upgrade().is_none())
 	/// ```
 	fn deref_mut(&mut self) -> &mut Self::Target {
-		pipeline!(&mut self.rc => Rc::make_mut)
+		#[cfg(feature = "strict-deref-mut")]
+		debug_assert!(
+			!Self::needs_cloning_to_mutate(self),
+			"dereferencing a shared CowRc<{}> mutably would clone it, use CowRc::make_mut to clone explicitly",
+			std::any::type_name::<T>(),
+		);
+		#[cfg(feature = "debug-alias")]
+		Self::report_alias(self);
+		Self::make_mut(self)
 	}
 }
 
@@ -225,11 +573,84 @@ impl<T: ?Sized> AsMut<Rc<T>> for CowRc<T> {
 	}
 }
 
+impl<T: ?Sized> Borrow<T> for CowRc<T> {
+	fn borrow(&self) -> &T {
+		self
+	}
+}
+
+impl<T: Clone> BorrowMut<T> for CowRc<T> {
+	/// Mutably borrows the inner value. Has the same effect as dereferencing it,
+	/// so it clones the inner value if this `CowRc` is shared.
+	///
+	/// See [`DerefMut` implementation for `CowRc`](CowRc::<T>::deref_mut) for details
+	fn borrow_mut(&mut self) -> &mut T {
+		self
+	}
+}
+
+impl io::Write for CowRc<Vec<u8>> {
+	/// Appends `buf` to the inner `Vec`, cloning it first if this `CowRc` is shared
+	fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+		pipeline!(&mut self.rc => Rc::make_mut).extend_from_slice(buf);
+		Ok(buf.len())
+	}
+
+	fn flush(&mut self) -> io::Result<()> {
+		Ok(())
+	}
+}
+
+/// Fluent construction of a [`CowRc`], like [`ToOwned::to_owned`] or [`ToString::to_string`]
+///
+/// ```
+/// use optimistic_mutation::rc::{CowRc, ToCowRc};
+///
+/// let number: CowRc<i32> = 5.to_cow_rc();
+/// let str: CowRc<str> = "Hello".to_cow_rc();
+/// let slice: CowRc<[i32]> = [1, 2][..].to_cow_rc();
+///
+/// assert_eq!(*number, 5);
+/// assert_eq!(&*str, "Hello");
+/// assert_eq!(&*slice, [1, 2]);
+/// ```
+pub trait ToCowRc {
+	fn to_cow_rc(&self) -> CowRc<Self>;
+}
+
+impl<T: Clone> ToCowRc for T {
+	fn to_cow_rc(&self) -> CowRc<Self> {
+		pipeline!(self |> Self::clone |> CowRc::new)
+	}
+}
+
+impl ToCowRc for str {
+	fn to_cow_rc(&self) -> CowRc<Self> {
+		CowRc::from(self)
+	}
+}
+
+impl<T: Clone> ToCowRc for [T] {
+	fn to_cow_rc(&self) -> CowRc<Self> {
+		CowRc::from(self)
+	}
+}
+
 impl<T> WeakCowRc<T> {
 	#[must_use]
 	pub const fn new() -> Self {
 		pipeline!(Weak::new() => Self::from_weak)
 	}
+
+	/// Upgrades `weak` if its value is alive, otherwise builds a new value with `f`
+	/// and points `weak` to it: a lazily (re)built value shared while someone holds it
+	pub fn upgrade_or_insert_with(weak: &mut Self, f: impl FnOnce() -> T) -> CowRc<T> {
+		weak.upgrade().unwrap_or_else(|| {
+			let strong = CowRc::new(f());
+			*weak = CowRc::downgrade(&strong);
+			strong
+		})
+	}
 }
 
 impl<T: ?Sized> WeakCowRc<T> {
@@ -244,10 +665,52 @@ impl<T: ?Sized> WeakCowRc<T> {
 	}
 }
 
+impl<T: ?Sized + Display> Display for WeakCowRc<T> {
+	/// Displays the value if it is still alive, `<dropped>` otherwise
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		match self.upgrade() {
+			Some(value) => Display::fmt(&*value, f),
+			None => f.write_str("<dropped>"),
+		}
+	}
+}
+
+impl<T: ?Sized> Clone for WeakCowRc<T> {
+	fn clone(&self) -> Self {
+		pipeline!(&self.weak => Weak::clone => Self::from_weak)
+	}
+}
+
+impl<T: Clone> ScopedMut<'_, T> {
+	/// Whether the value was cloned because it was shared when written through this guard
+	#[must_use]
+	pub const fn cloned(this: &Self) -> bool {
+		this.cloned
+	}
+}
+
+impl<T: Clone> Deref for ScopedMut<'_, T> {
+	type Target = T;
+
+	fn deref(&self) -> &Self::Target {
+		self.cow
+	}
+}
+
+impl<T: Clone> DerefMut for ScopedMut<'_, T> {
+	/// Clones the value if the `CowRc` is shared, see [`CowRc::make_mut`]
+	fn deref_mut(&mut self) -> &mut Self::Target {
+		self.cloned |= CowRc::needs_cloning_to_mutate(self.cow);
+		#[cfg(feature = "debug-alias")]
+		CowRc::report_alias(self.cow);
+		CowRc::make_mut(self.cow)
+	}
+}
+
 #[cfg(test)]
 mod tests {
-	use crate::rc::CowRc;
-	use std::{ops::DerefMut, rc::Rc};
+	use crate::rc::{CowRc, ScopedMut, WeakCowRc};
+	use std::{borrow::BorrowMut, cell::RefCell, ops::DerefMut, ptr, rc::Rc};
 	use sugaru::pipeline;
 
 	#[derive(Debug, Clone)]
@@ -263,6 +726,7 @@ mod tests {
 	}
 
 	#[test]
+	#[cfg_attr(feature = "strict-deref-mut", ignore = "clones through DerefMut")]
 	fn sandbox() {
 		let person1 = Person {
 			age: 46,
@@ -323,4 +787,547 @@ mod tests {
 		unique_cow_rc.int += 1;
 		assert!(weak_cow_rc.upgrade().is_none());
 	}
+
+	#[test]
+	#[cfg_attr(feature = "strict-deref-mut", ignore = "clones through DerefMut")]
+	fn borrow_mut() {
+		fn increment<B: BorrowMut<i32>>(mut borrowed: B) -> B {
+			*borrowed.borrow_mut() += 1;
+			borrowed
+		}
+
+		let unique = CowRc::new(1);
+		let unique_ptr = Rc::as_ptr(&unique.rc);
+		let unique = increment(unique);
+		assert_eq!(*unique, 2);
+		assert_eq!(Rc::as_ptr(&unique.rc), unique_ptr); // No clone
+
+		let shared = CowRc::new(1);
+		let incremented = increment(shared.clone());
+		assert_eq!(*incremented, 2);
+		assert_eq!(*shared, 1);
+	}
+
+	#[test]
+	fn make_mut_with_capacity() {
+		let mut unique = CowRc::new(vec![1, 2]);
+		CowRc::make_mut_with_capacity(&mut unique, 10).push(3);
+		assert!(unique.capacity() >= 12);
+
+		let original = CowRc::new(vec![1, 2]);
+		let mut shared = original.clone();
+		let vec = CowRc::make_mut_with_capacity(&mut shared, 10);
+		assert!(vec.capacity() >= 12);
+		vec.push(3);
+		assert_eq!(*shared, [1, 2, 3]);
+		assert_eq!(*original, [1, 2]);
+	}
+
+	#[test]
+	fn arithmetic() {
+		let six: CowRc<i64> = CowRc::new(2) * CowRc::new(3);
+		let shared = CowRc::new(4);
+		assert_eq!(*(six.clone() + shared.clone()), 10);
+		assert_eq!(*(six.clone() - shared.clone()), 2);
+		assert_eq!(*(CowRc::new(9) / shared.clone()), 2);
+		assert_eq!(*(CowRc::new(9) % shared.clone()), 1);
+		assert_eq!((*six, *shared), (6, 4));
+	}
+
+	#[test]
+	fn arithmetic_moves_unique_operands() {
+		#[derive(Debug, PartialEq)]
+		struct TrapClone(i64);
+
+		impl Clone for TrapClone {
+			fn clone(&self) -> Self {
+				panic!("Test failed: clone was called")
+			}
+		}
+
+		impl std::ops::Add for TrapClone {
+			type Output = Self;
+
+			fn add(self, rhs: Self) -> Self {
+				Self(self.0 + rhs.0)
+			}
+		}
+
+		let sum = CowRc::new(TrapClone(1)) + CowRc::new(TrapClone(2));
+		assert_eq!(*sum, TrapClone(3));
+	}
+
+	#[test]
+	fn leak() {
+		let table: &'static [u8] = CowRc::leak(CowRc::from([1, 2, 3]));
+		assert_eq!(table, [1, 2, 3]);
+
+		let value = CowRc::new(5);
+		let clone = value.clone();
+		let leaked: &'static i32 = CowRc::leak(value);
+		drop(clone);
+		assert_eq!(*leaked, 5);
+	}
+
+	#[test]
+	#[cfg(feature = "nightly")]
+	fn try_new() {
+		let five = CowRc::try_new(5).unwrap();
+		assert_eq!(*five, 5);
+		assert!(CowRc::is_unique(&five));
+	}
+
+	#[test]
+	fn assert_unique() {
+		let unique = CowRc::new(1);
+		CowRc::assert_unique(&unique);
+	}
+
+	#[test]
+	#[cfg(debug_assertions)]
+	#[should_panic(expected = "CowRc<i32> is not unique")]
+	fn assert_unique_panics_when_shared() {
+		let shared = CowRc::new(1);
+		let _clone = shared.clone();
+		CowRc::assert_unique(&shared);
+	}
+
+	#[test]
+	fn io_write() {
+		use std::io::Write;
+
+		let original = CowRc::new(b"Hello".to_vec());
+		let mut buffer = original.clone();
+		let name = "world";
+		write!(buffer, ", {name}!").unwrap();
+		buffer.write_all(b"!").unwrap();
+		buffer.flush().unwrap();
+
+		assert_eq!(&**buffer, b"Hello, world!!");
+		assert_eq!(&**original, b"Hello");
+	}
+
+	#[test]
+	fn from_reference() {
+		fn convert<C: for<'a> From<&'a CowRc<str>>>(cow: &CowRc<str>) -> C {
+			C::from(cow)
+		}
+
+		let original: CowRc<str> = CowRc::from("Hello");
+		let converted: CowRc<str> = convert(&original);
+		assert_eq!(Rc::as_ptr(&original.rc), Rc::as_ptr(&converted.rc));
+		assert_eq!(Rc::strong_count(&original.rc), 2);
+	}
+
+	#[test]
+	fn sharing() {
+		use crate::sharing::Sharing;
+
+		let value = CowRc::new(1);
+		assert_eq!(CowRc::sharing(&value), Sharing::Unique);
+		let weak = CowRc::downgrade(&value);
+		assert_eq!(CowRc::sharing(&value), Sharing::Observed);
+		let clone = value.clone();
+		assert_eq!(CowRc::sharing(&value), Sharing::Shared);
+		drop((weak, clone));
+		assert_eq!(CowRc::sharing(&value), Sharing::Unique);
+	}
+
+	#[test]
+	fn display_weak() {
+		let value = CowRc::new(5);
+		let weak = CowRc::downgrade(&value);
+		assert_eq!(weak.to_string(), "5");
+		drop(value);
+		assert_eq!(weak.to_string(), "<dropped>");
+	}
+
+	#[test]
+	fn hash() {
+		use std::hash::{BuildHasher, Hash, Hasher, RandomState};
+
+		trait Shape {
+			fn sides(&self) -> u32;
+		}
+
+		struct Square;
+
+		impl Shape for Square {
+			fn sides(&self) -> u32 {
+				4
+			}
+		}
+
+		impl Hash for dyn Shape {
+			fn hash<H: Hasher>(&self, state: &mut H) {
+				self.sides().hash(state);
+			}
+		}
+
+		let state = RandomState::new();
+		let str: CowRc<str> = CowRc::from("Hello");
+		assert_eq!(state.hash_one(&str), state.hash_one("Hello"));
+		assert_eq!(state.hash_one(CowRc::new(5)), state.hash_one(5));
+
+		let shape: CowRc<dyn Shape> = CowRc::from_rc(Rc::new(Square));
+		assert_eq!(state.hash_one(&shape), state.hash_one(4_u32));
+	}
+
+	#[test]
+	fn try_map() {
+		#[derive(Debug)]
+		struct TrapClone(String);
+
+		impl Clone for TrapClone {
+			fn clone(&self) -> Self {
+				panic!("Test failed: clone was called")
+			}
+		}
+
+		let text = CowRc::new(String::from("42"));
+		let number = CowRc::try_map(text.clone(), |text| text.parse::<i32>());
+		assert_eq!(number.map(|number| *number), Ok(42));
+		assert_eq!(*text, "42");
+
+		let invalid = CowRc::new(String::from("abc"));
+		assert!(CowRc::try_map(invalid, |text| text.parse::<i32>()).is_err());
+
+		let unique = CowRc::new(TrapClone(String::from("7")));
+		let parsed = CowRc::try_map(unique, |TrapClone(text)| text.parse::<u8>()).unwrap();
+		assert_eq!(*parsed, 7);
+	}
+
+	#[test]
+	fn detect_cycle() {
+		struct Node {
+			edges: RefCell<Vec<WeakCowRc<Self>>>,
+		}
+		let new_node = || {
+			CowRc::new(Node {
+				edges: RefCell::new(Vec::new()),
+			})
+		};
+		let edges = |node: &Node| node.edges.borrow().clone();
+
+		let (a, b, c) = (new_node(), new_node(), new_node());
+		a.edges.borrow_mut().push(CowRc::downgrade(&b));
+		b.edges.borrow_mut().push(CowRc::downgrade(&c));
+		b.edges.borrow_mut().push(CowRc::downgrade(&b)); // A cycle which doesn't contain `a`
+		assert!(!CowRc::detect_cycle(&a, edges));
+		assert!(CowRc::detect_cycle(&b, edges));
+
+		c.edges.borrow_mut().push(CowRc::downgrade(&a));
+		assert!(CowRc::detect_cycle(&a, edges));
+		assert!(CowRc::detect_cycle(&c, edges));
+
+		drop(b); // Breaks the cycle
+		assert!(!CowRc::detect_cycle(&a, edges));
+		assert!(!CowRc::detect_cycle(&c, edges));
+	}
+
+	#[test]
+	#[cfg(all(feature = "strict-deref-mut", debug_assertions))]
+	#[should_panic = "would clone"]
+	fn strict_deref_mut_shared() {
+		let mut data = CowRc::new(1);
+		let _clone = data.clone();
+		*data += 1;
+	}
+
+	#[test]
+	#[cfg(feature = "strict-deref-mut")]
+	fn strict_deref_mut_unique() {
+		let mut data = CowRc::new(1);
+		let weak = CowRc::downgrade(&data);
+		*data += 1; // Moves the value out of the weak pointers' reach, but doesn't clone
+		assert_eq!(*data, 2);
+		drop(weak);
+
+		let clone = data.clone();
+		*CowRc::make_mut(&mut data) += 1; // Explicit clone
+		assert_eq!((*data, *clone), (3, 2));
+	}
+
+	#[test]
+	fn swap() {
+		let mut a = CowRc::new(1);
+		let mut b = CowRc::new(2);
+		let weak = CowRc::downgrade(&a);
+
+		CowRc::swap(&mut a, &mut b);
+		assert_eq!((*a, *b), (2, 1));
+		assert_eq!(*weak.upgrade().unwrap(), 1); // Follows the allocation, now in `b`
+		drop(weak);
+
+		let addresses = (ptr::from_ref(&*a), ptr::from_ref(&*b));
+		CowRc::swap_contents(&mut a, &mut b);
+		assert_eq!((*a, *b), (1, 2));
+		assert_eq!((ptr::from_ref(&*a), ptr::from_ref(&*b)), addresses); // Unique, swapped in place
+
+		let shared = b.clone();
+		CowRc::swap_contents(&mut a, &mut b);
+		assert_eq!((*a, *b, *shared), (2, 1, 2)); // `b` was cloned before the swap
+	}
+
+	#[test]
+	fn as_rc() {
+		fn shared_len(rc: &Rc<str>) -> (usize, usize) {
+			(rc.len(), Rc::strong_count(rc))
+		}
+
+		let hello: CowRc<str> = CowRc::from("Hello");
+		let clone = hello.clone();
+		assert_eq!(shared_len(CowRc::as_rc(&hello)), (5, 2));
+		drop(clone);
+		assert_eq!(shared_len(CowRc::as_rc(&hello)), (5, 1));
+	}
+
+	#[test]
+	fn unwrap_or_default() {
+		let unique = CowRc::new(String::from("unique"));
+		assert_eq!(CowRc::unwrap_or_default(unique), "unique");
+
+		let shared = CowRc::new(String::from("shared"));
+		let other = shared.clone();
+		assert_eq!(CowRc::unwrap_or_default(shared), "shared"); // Cloned
+		assert_eq!(*other, "shared"); // Still owned by `other`
+
+		let last = other.clone();
+		drop(other);
+		assert_eq!(CowRc::unwrap_or_default(last), "shared");
+	}
+
+	#[test]
+	fn rc_conversions() {
+		let rc: Rc<str> = Rc::from("shared");
+		let cow: CowRc<str> = CowRc::from(Rc::clone(&rc));
+		assert_eq!(Rc::strong_count(&rc), 2);
+
+		let back: Rc<str> = cow.into();
+		assert_eq!(Rc::strong_count(&rc), 2); // Moved, not cloned
+		assert!(Rc::ptr_eq(&back, &rc));
+		drop(back);
+		assert_eq!(Rc::strong_count(&rc), 1);
+	}
+
+	#[test]
+	fn upgrade_or_insert_with() {
+		let mut cache = WeakCowRc::new();
+		let first = WeakCowRc::upgrade_or_insert_with(&mut cache, || 1);
+		let alive = WeakCowRc::upgrade_or_insert_with(&mut cache, || 2);
+		assert!(CowRc::ptr_eq(&first, &alive));
+		assert_eq!(*alive, 1);
+
+		drop((first, alive));
+		assert!(cache.upgrade().is_none());
+		let rebuilt = WeakCowRc::upgrade_or_insert_with(&mut cache, || 3);
+		assert_eq!(*rebuilt, 3);
+		assert_eq!(*cache.upgrade().unwrap(), 3);
+	}
+
+	#[test]
+	fn cmp_by_ptr() {
+		let a: CowRc<str> = CowRc::from("same");
+		let b: CowRc<str> = CowRc::from("same");
+		assert_eq!(CowRc::cmp_by_ptr(&a, &a.clone()), std::cmp::Ordering::Equal);
+		assert_eq!(
+			CowRc::cmp_by_ptr(&a, &b),
+			CowRc::cmp_by_ptr(&b, &a).reverse()
+		);
+		assert_ne!(CowRc::cmp_by_ptr(&a, &b), std::cmp::Ordering::Equal); // Equal values, different identities
+
+		let mut nodes = vec![b.clone(), a.clone(), b, a];
+		nodes.sort_by(CowRc::cmp_by_ptr);
+		nodes.dedup_by(|x, y| CowRc::ptr_eq(x, y));
+		assert_eq!(nodes.len(), 2);
+	}
+
+	#[test]
+	fn modify() {
+		#[derive(Debug, Clone, PartialEq)]
+		struct Config {
+			name: String,
+			retries: u8,
+		}
+
+		let original = CowRc::new(Config {
+			name: "default".to_owned(),
+			retries: 1,
+		});
+		let mut config = original.clone();
+		let address = std::ptr::from_ref(&*original);
+		config
+			.modify(|config| config.name.push_str("-custom"))
+			.modify(|config| config.retries += 2);
+		let cloned = std::ptr::from_ref(&*config);
+		assert_ne!(cloned, address); // Cloned by the first edit
+		config.modify(|config| config.retries *= 2);
+		assert_eq!(std::ptr::from_ref(&*config), cloned); // Unique afterwards
+
+		assert_eq!(CowRc::map_ref(&config, |config| config.retries), 6);
+		assert_eq!(config.name, "default-custom");
+		assert_eq!(CowRc::map_ref(&original, |config| config.retries), 1);
+	}
+
+	#[test]
+	fn eq_short_circuits_on_identity() {
+		thread_local! {
+			static COMPARISONS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+		}
+
+		#[derive(Debug, Eq)]
+		struct Large(Vec<i32>);
+
+		impl PartialEq for Large {
+			fn eq(&self, other: &Self) -> bool {
+				COMPARISONS.set(COMPARISONS.get() + 1);
+				self.0 == other.0
+			}
+		}
+
+		let value = CowRc::new(Large(vec![1; 1000]));
+		assert_eq!(value, value.clone()); // Aliased
+		assert_eq!(COMPARISONS.get(), 0);
+		assert_eq!(value, CowRc::new(Large(vec![1; 1000]))); // Distinct but equal
+		assert_ne!(value, CowRc::new(Large(vec![2; 1000])));
+		assert_eq!(COMPARISONS.get(), 2);
+
+		let nan = CowRc::new(f64::NAN);
+		assert_ne!(nan, nan.clone()); // Not `Eq`: compared by value
+	}
+
+	#[test]
+	fn replace() {
+		let mut current = CowRc::new(String::from("old"));
+		let other = current.clone();
+		let previous = CowRc::replace(&mut current, String::from("new"));
+		assert_eq!((&**previous, &**current), ("old", "new"));
+		assert!(CowRc::ptr_eq(&previous, &other)); // Not cloned
+	}
+
+	#[test]
+	fn rewrite() {
+		enum Tree {
+			Leaf(i32),
+			Node(CowRc<Self>, CowRc<Self>),
+		}
+
+		/// Doubles the negative leaves, sharing the subtrees without any
+		fn double_negatives(tree: &Tree) -> Option<Tree> {
+			match tree {
+				Tree::Leaf(value) if *value < 0 => Some(Tree::Leaf(value * 2)),
+				Tree::Leaf(_) => None,
+				Tree::Node(left, right) => {
+					let new_left = CowRc::rewrite(left, double_negatives);
+					let new_right = CowRc::rewrite(right, double_negatives);
+					let unchanged =
+						CowRc::ptr_eq(left, &new_left) && CowRc::ptr_eq(right, &new_right);
+					(!unchanged).then_some(Tree::Node(new_left, new_right))
+				}
+			}
+		}
+
+		let positive = CowRc::new(Tree::Node(
+			CowRc::new(Tree::Leaf(1)),
+			CowRc::new(Tree::Leaf(2)),
+		));
+		let root = CowRc::new(Tree::Node(positive.clone(), CowRc::new(Tree::Leaf(-3))));
+
+		let rewritten = CowRc::rewrite(&root, double_negatives);
+		assert!(!CowRc::ptr_eq(&rewritten, &root)); // Changed: a new node
+		let Tree::Node(left, right) = &*rewritten else {
+			panic!("the root is a node")
+		};
+		assert!(CowRc::ptr_eq(left, &positive)); // Unchanged subtree, shared
+		assert!(matches!(**right, Tree::Leaf(-6)));
+
+		let again = CowRc::rewrite(&positive, double_negatives);
+		assert!(CowRc::ptr_eq(&again, &positive));
+	}
+
+	#[test]
+	fn inspect() {
+		let mut seen = None;
+		let value = CowRc::inspect(CowRc::new(5), |value| seen = Some(*value));
+		assert_eq!(seen, Some(5));
+		assert_eq!(*value, 5);
+	}
+
+	#[test]
+	fn add_scalar() {
+		let mut unique = CowRc::new(vec![1, 2, 3]);
+		CowRc::add_scalar(&mut unique, 10);
+		assert_eq!(*unique, [11, 12, 13]);
+
+		let original = CowRc::new(vec![1.5, 2.5]);
+		let mut shared = original.clone();
+		CowRc::add_scalar(&mut shared, 1.0);
+		assert_eq!(*shared, [2.5, 3.5]);
+		assert_eq!(*original, [1.5, 2.5]);
+	}
+
+	#[test]
+	fn map_option() {
+		let some = CowRc::new(Some(String::from("present")));
+		let value = CowRc::map_option(&some).unwrap();
+		assert_eq!(*value, "present");
+		assert_eq!(*some, Some(String::from("present")));
+
+		let none = CowRc::<Option<String>>::new(None);
+		assert!(CowRc::map_option(&none).is_none());
+	}
+
+	#[test]
+	fn scoped_mut() {
+		let mut shared = CowRc::new(vec![1, 2]);
+		let original = shared.clone();
+		{
+			let guard = CowRc::scoped_mut(&mut shared);
+			assert_eq!(guard.len(), 2);
+			assert!(!ScopedMut::cloned(&guard));
+		}
+		assert!(CowRc::ptr_eq(&shared, &original)); // Never written, never cloned
+
+		{
+			let mut guard = CowRc::scoped_mut(&mut shared);
+			guard.push(3);
+			guard.push(4);
+			assert!(ScopedMut::cloned(&guard));
+		}
+		assert_eq!(*shared, [1, 2, 3, 4]);
+		assert_eq!(*original, [1, 2]);
+
+		let mut guard = CowRc::scoped_mut(&mut shared);
+		guard.clear();
+		assert!(!ScopedMut::cloned(&guard)); // Unique by now
+	}
+
+	#[test]
+	fn assign_into() {
+		let source = CowRc::new(vec![1, 2, 3]);
+		let mut dest = CowRc::new(Vec::with_capacity(10));
+		let address = ptr::from_ref(&*dest);
+		let buffer = dest.as_ptr();
+		CowRc::assign_into(&source, &mut dest);
+		assert_eq!(*dest, [1, 2, 3]);
+		assert_eq!(ptr::from_ref(&*dest), address); // Same Rc allocation
+		assert_eq!(dest.as_ptr(), buffer); // Same Vec buffer
+		assert!(!CowRc::ptr_eq(&source, &dest));
+
+		let other = dest.clone();
+		CowRc::assign_into(&source, &mut dest);
+		assert!(CowRc::ptr_eq(&source, &dest)); // dest was shared: now shares source instead
+		assert_eq!(*other, [1, 2, 3]);
+	}
+
+	#[test]
+	fn fork() {
+		let original = CowRc::new(vec![1, 2]);
+		let shared = original.clone();
+		let fork = CowRc::fork(&original);
+		assert!(CowRc::is_unique(&fork));
+		assert!(!CowRc::ptr_eq(&fork, &original));
+		assert_eq!(fork, original);
+		assert!(CowRc::ptr_eq(&shared, &original));
+	}
 }
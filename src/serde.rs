@@ -0,0 +1,450 @@
+use crate::{
+	rc::CowRc,
+	to_owned::{cow_rc_slice::ToCowRcSlice, cow_rc_str::ToCowRcStr},
+	vec::{CowArcVec, CowRcVec},
+};
+use ::serde::{
+	de::{DeserializeOwned, Error, Visitor},
+	Deserialize, Deserializer, Serialize, Serializer,
+};
+use serde_json::{Map, Value};
+use std::fmt::Formatter;
+
+impl Serialize for ToCowRcStr {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		self.str.serialize(serializer)
+	}
+}
+
+impl<T: Serialize> Serialize for ToCowRcSlice<T> {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		self.slice.serialize(serializer)
+	}
+}
+
+impl<'de> Deserialize<'de> for Box<ToCowRcStr> {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		Box::<str>::deserialize(deserializer).map(Self::from)
+	}
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Box<ToCowRcSlice<T>> {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		Box::<[T]>::deserialize(deserializer).map(Self::from)
+	}
+}
+
+impl<T: Serialize> Serialize for CowRcVec<T> {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		(**self).serialize(serializer)
+	}
+}
+
+impl<T: Serialize> Serialize for CowArcVec<T> {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		(**self).serialize(serializer)
+	}
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for CowRcVec<T> {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		Vec::<T>::deserialize(deserializer).map(Self::from)
+	}
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for CowArcVec<T> {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		Vec::<T>::deserialize(deserializer).map(Self::from)
+	}
+}
+
+/// Deserializes the string through a visitor, so that formats holding it contiguously
+/// (e.g. JSON without escapes) lend a borrowed `&str`, copied once into the `Rc` allocation
+/// instead of going through an intermediate `String`
+impl<'de> Deserialize<'de> for CowRc<str> {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		deserializer.deserialize_str(CowRcStrVisitor)
+	}
+}
+
+struct CowRcStrVisitor;
+
+impl Visitor<'_> for CowRcStrVisitor {
+	type Value = CowRc<str>;
+
+	fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+		formatter.write_str("a string")
+	}
+
+	/// Called with borrowed and transient strings alike
+	fn visit_str<E: Error>(self, string: &str) -> Result<Self::Value, E> {
+		Ok(CowRc::from(string))
+	}
+}
+
+/// Applies a JSON merge patch ([RFC 7386](https://www.rfc-editor.org/rfc/rfc7386)) to `value`.
+///
+/// The inner value is never cloned: a unique value is overwritten in place,
+/// a shared one is replaced by a new allocation, leaving the other owners untouched
+///
+/// # Errors
+///
+/// Returns the error of serializing the value or of deserializing the patched value,
+/// in which case `value` is left untouched
+pub fn apply_patch<T: Clone + DeserializeOwned + Serialize>(
+	value: &mut CowRc<T>,
+	patch: &Value,
+) -> Result<(), serde_json::Error> {
+	let mut json = serde_json::to_value(&**value)?;
+	merge_patch(&mut json, patch);
+	let patched = serde_json::from_value(json)?;
+	if CowRc::needs_cloning_to_mutate(value) {
+		*value = CowRc::new(patched);
+	} else {
+		*CowRc::make_mut(value) = patched;
+	}
+	Ok(())
+}
+
+fn merge_patch(target: &mut Value, patch: &Value) {
+	match (target, patch) {
+		(Value::Object(target), Value::Object(patch)) => {
+			for (key, value) in patch {
+				if value.is_null() {
+					target.remove(key);
+				} else {
+					merge_patch(target.entry(key.clone()).or_insert(Value::Null), value);
+				}
+			}
+		}
+		(target, Value::Object(_)) => {
+			*target = Value::Object(Map::new());
+			merge_patch(target, patch);
+		}
+		(target, patch) => *target = patch.clone(),
+	}
+}
+
+pub mod weak_as_option {
+	//! Serializes a [`WeakCowRc<T>`] as an `Option<T>`: the upgraded value, or `null` when dropped.
+	//!
+	//! ```ignore
+	//! #[derive(Serialize, Deserialize)]
+	//! struct Node {
+	//! 	#[serde(with = "optimistic_mutation::serde::weak_as_option")]
+	//! 	parent: WeakCowRc<Node>,
+	//! }
+	//! ```
+	//!
+	//! A weak pointer doesn't own its value, so deserializing always gives a dangling `WeakCowRc`
+	//! (the serialized value, if any, is skipped)
+
+	use crate::rc::WeakCowRc;
+	use ::serde::{de::IgnoredAny, Deserialize, Deserializer, Serialize, Serializer};
+
+	pub fn serialize<T, S>(weak: &WeakCowRc<T>, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		T: ?Sized + Serialize,
+		S: Serializer,
+	{
+		weak.upgrade().as_deref().serialize(serializer)
+	}
+
+	pub fn deserialize<'de, T, D>(deserializer: D) -> Result<WeakCowRc<T>, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		Option::<IgnoredAny>::deserialize(deserializer).map(|_| WeakCowRc::new())
+	}
+}
+
+pub mod arc_weak_as_option {
+	//! Serializes a [`WeakCowArc<T>`] as an `Option<T>`, like [`weak_as_option`](super::weak_as_option)
+	//! for the thread-safe pointers.
+	//!
+	//! ```ignore
+	//! #[derive(Serialize, Deserialize)]
+	//! struct Node {
+	//!     #[serde(with = "optimistic_mutation::serde::arc_weak_as_option")]
+	//!     parent: WeakCowArc<Node>,
+	//! }
+	//! ```
+	//!
+	//! Deserializing always gives a dangling `WeakCowArc` (the serialized value, if any, is skipped)
+
+	use crate::sync::WeakCowArc;
+	use ::serde::{de::IgnoredAny, Deserialize, Deserializer, Serialize, Serializer};
+
+	pub fn serialize<T, S>(weak: &WeakCowArc<T>, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		T: ?Sized + Serialize,
+		S: Serializer,
+	{
+		WeakCowArc::upgrade(weak).as_deref().serialize(serializer)
+	}
+
+	pub fn deserialize<'de, T, D>(deserializer: D) -> Result<WeakCowArc<T>, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		Option::<IgnoredAny>::deserialize(deserializer).map(|_| WeakCowArc::new())
+	}
+}
+
+pub mod interned {
+	//! Deserializes strings into [`CowRc<str>`] deduplicated by a [`StrInterner`],
+	//! so that parsing many objects shares their repeated keys
+	//!
+	//! ```ignore
+	//! let mut interner = StrInterner::new();
+	//! let mut deserializer = serde_json::Deserializer::from_str(json);
+	//! let map: HashMap<CowRc<str>, i32> =
+	//! 	InternedKeysMap::new(&mut interner).deserialize(&mut deserializer)?;
+	//! ```
+
+	use crate::{intern::StrInterner, rc::CowRc};
+	use ::serde::de::{Deserialize, DeserializeSeed, Deserializer, Error, MapAccess, Visitor};
+	use std::{collections::HashMap, fmt::Formatter, marker::PhantomData};
+
+	/// Deserializes a string, interned in the given [`StrInterner`]
+	pub struct InternedStr<'i>(pub &'i mut StrInterner);
+
+	impl<'de> DeserializeSeed<'de> for InternedStr<'_> {
+		type Value = CowRc<str>;
+
+		fn deserialize<D: Deserializer<'de>>(
+			self,
+			deserializer: D,
+		) -> Result<Self::Value, D::Error> {
+			deserializer.deserialize_str(self)
+		}
+	}
+
+	impl Visitor<'_> for InternedStr<'_> {
+		type Value = CowRc<str>;
+
+		fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+			formatter.write_str("a string")
+		}
+
+		fn visit_str<E: Error>(self, string: &str) -> Result<Self::Value, E> {
+			Ok(self.0.intern(string))
+		}
+	}
+
+	/// Deserializes a map whose keys are interned in the given [`StrInterner`]
+	pub struct InternedKeysMap<'i, V> {
+		interner: &'i mut StrInterner,
+		value: PhantomData<V>,
+	}
+
+	impl<'i, V> InternedKeysMap<'i, V> {
+		#[must_use]
+		pub fn new(interner: &'i mut StrInterner) -> Self {
+			Self {
+				interner,
+				value: PhantomData,
+			}
+		}
+	}
+
+	impl<'de, V: Deserialize<'de>> DeserializeSeed<'de> for InternedKeysMap<'_, V> {
+		type Value = HashMap<CowRc<str>, V>;
+
+		fn deserialize<D: Deserializer<'de>>(
+			self,
+			deserializer: D,
+		) -> Result<Self::Value, D::Error> {
+			deserializer.deserialize_map(self)
+		}
+	}
+
+	impl<'de, V: Deserialize<'de>> Visitor<'de> for InternedKeysMap<'_, V> {
+		type Value = HashMap<CowRc<str>, V>;
+
+		fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+			formatter.write_str("a map with string keys")
+		}
+
+		fn visit_map<A: MapAccess<'de>>(self, mut access: A) -> Result<Self::Value, A::Error> {
+			let mut map = HashMap::with_capacity(access.size_hint().unwrap_or(0));
+			while let Some(key) = access.next_key_seed(InternedStr(&mut *self.interner))? {
+				map.insert(key, access.next_value()?);
+			}
+			Ok(map)
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		rc::{CowRc, WeakCowRc},
+		sync::{CowArc, WeakCowArc},
+	};
+	use serde_json::json;
+	use std::ptr;
+
+	#[derive(Debug, Serialize, Deserialize)]
+	struct Node {
+		value: i32,
+		#[serde(with = "crate::serde::weak_as_option")]
+		parent: WeakCowRc<Node>,
+	}
+
+	#[test]
+	fn weak_as_option() {
+		let root = CowRc::new(Node {
+			value: 1,
+			parent: WeakCowRc::new(),
+		});
+		let child = Node {
+			value: 2,
+			parent: CowRc::downgrade(&root),
+		};
+
+		let json = serde_json::to_string(&child).unwrap();
+		assert_eq!(json, r#"{"value":2,"parent":{"value":1,"parent":null}}"#);
+
+		drop(root);
+		let json = serde_json::to_string(&child).unwrap();
+		assert_eq!(json, r#"{"value":2,"parent":null}"#);
+
+		let node: Node =
+			serde_json::from_str(r#"{"value":2,"parent":{"value":1,"parent":null}}"#).unwrap();
+		assert_eq!(node.value, 2);
+		assert!(node.parent.upgrade().is_none());
+	}
+
+	#[test]
+	fn arc_weak_as_option() {
+		#[derive(Serialize, Deserialize)]
+		struct Task {
+			id: u32,
+			#[serde(with = "crate::serde::arc_weak_as_option")]
+			parent: WeakCowArc<u32>,
+		}
+
+		let parent = CowArc::new(1);
+		let task = Task {
+			id: 2,
+			parent: CowArc::downgrade(&parent),
+		};
+		assert_eq!(
+			serde_json::to_string(&task).unwrap(),
+			r#"{"id":2,"parent":1}"#
+		);
+		drop(parent);
+		assert_eq!(
+			serde_json::to_string(&task).unwrap(),
+			r#"{"id":2,"parent":null}"#
+		);
+
+		let task: Task = serde_json::from_str(r#"{"id":2,"parent":1}"#).unwrap();
+		assert_eq!(task.id, 2);
+		assert!(WeakCowArc::upgrade(&task.parent).is_none());
+	}
+
+	#[test]
+	fn owner_types_round_trip() {
+		#[derive(Debug, PartialEq, Serialize, Deserialize)]
+		struct Record {
+			name: Box<ToCowRcStr>,
+			values: Box<ToCowRcSlice<i32>>,
+		}
+
+		let record = Record {
+			name: Box::from("Hello"),
+			values: Box::from(vec![1, 2, 3]),
+		};
+		let json = serde_json::to_string(&record).unwrap();
+		assert_eq!(json, r#"{"name":"Hello","values":[1,2,3]}"#);
+		assert_eq!(serde_json::from_str::<Record>(&json).unwrap(), record);
+	}
+
+	#[test]
+	fn vec_round_trip() {
+		#[derive(Debug, PartialEq, Serialize, Deserialize)]
+		struct Buffers {
+			local: CowRcVec<u8>,
+			shared: CowArcVec<String>,
+		}
+
+		let buffers = Buffers {
+			local: CowRcVec::from(vec![1, 2, 3]),
+			shared: CowArcVec::from(vec![String::from("a")]),
+		};
+		let json = serde_json::to_string(&buffers).unwrap();
+		assert_eq!(json, r#"{"local":[1,2,3],"shared":["a"]}"#);
+		assert_eq!(serde_json::from_str::<Buffers>(&json).unwrap(), buffers);
+	}
+
+	#[test]
+	fn apply_patch() {
+		#[derive(Debug, Clone, Serialize, Deserialize)]
+		struct Document {
+			title: String,
+			tags: Vec<String>,
+			draft: Option<bool>,
+		}
+
+		let mut document = CowRc::new(Document {
+			title: "Draft".to_owned(),
+			tags: vec!["a".to_owned()],
+			draft: Some(true),
+		});
+		let original = document.clone();
+		super::apply_patch(&mut document, &json!({"title": "Final", "draft": null})).unwrap();
+		assert_eq!(document.title, "Final");
+		assert_eq!(document.tags, ["a"]);
+		assert_eq!(document.draft, None);
+		assert_eq!(original.title, "Draft"); // The shared original is untouched
+		assert_eq!(original.draft, Some(true));
+
+		let address = ptr::from_ref(&*document);
+		super::apply_patch(&mut document, &json!({"tags": ["b"]})).unwrap();
+		assert_eq!(document.tags, ["b"]);
+		assert_eq!(ptr::from_ref(&*document), address); // Unique, patched in place
+
+		assert!(super::apply_patch(&mut document, &json!({"title": 1})).is_err());
+		assert_eq!(document.title, "Final");
+	}
+
+	#[test]
+	fn deserialize_cow_rc_str() {
+		let borrowed: CowRc<str> = serde_json::from_str(r#""Hello""#).unwrap();
+		assert_eq!(&*borrowed, "Hello");
+		let escaped: CowRc<str> = serde_json::from_str(r#""Hello\nWorld""#).unwrap();
+		assert_eq!(&*escaped, "Hello\nWorld");
+
+		let reader: Vec<CowRc<str>> = serde_json::from_reader(&br#"["a","b"]"#[..]).unwrap();
+		assert_eq!(reader.concat(), "ab");
+		assert!(serde_json::from_str::<CowRc<str>>("1").is_err());
+	}
+
+	#[test]
+	fn interned_keys() {
+		use crate::intern::StrInterner;
+		use ::serde::de::DeserializeSeed;
+		use interned::InternedKeysMap;
+		use std::collections::HashMap;
+
+		let mut interner = StrInterner::new();
+		let mut maps: Vec<HashMap<CowRc<str>, i32>> = Vec::new();
+		for json in [r#"{"name":1,"age":2}"#, r#"{"age":3,"name":4}"#] {
+			let mut deserializer = serde_json::Deserializer::from_str(json);
+			maps.push(
+				InternedKeysMap::new(&mut interner)
+					.deserialize(&mut deserializer)
+					.unwrap(),
+			);
+		}
+
+		let first_key = maps[0].get_key_value("name").unwrap().0;
+		let second_key = maps[1].get_key_value("name").unwrap().0;
+		assert!(CowRc::ptr_eq(first_key, second_key));
+		assert_eq!(maps[1]["name"], 4);
+		assert_eq!(interner.len(), 2);
+	}
+}
@@ -0,0 +1,12 @@
+/// The sharing state of a [`CowRc`](crate::rc::CowRc) or [`CowArc`](crate::sync::CowArc),
+/// telling what mutating it would do
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Sharing {
+	/// No other strong nor weak pointer: mutation happens in place
+	Unique,
+	/// Other strong pointers exist: mutation clones the value
+	Shared,
+	/// Only weak pointers exist besides this one: mutation moves the value,
+	/// disassociating the weak pointers
+	Observed,
+}
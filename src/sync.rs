@@ -1,17 +1,31 @@
 use std::{
-	fmt::Debug,
-	ops::{Deref, DerefMut},
-	sync::{Arc, Weak},
+	borrow::{Borrow, BorrowMut},
+	cmp::Ordering,
+	fmt::{Debug, Display, Formatter},
+	hash::{Hash, Hasher},
+	hint, io, mem,
+	ops::{Add, AddAssign, Deref, DerefMut, Div, Mul, Rem, Sub},
+	rc::Rc,
+	sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError, Weak},
 };
 
+use crate::{frozen::Frozen, rc::CowRc, sharing::Sharing};
 use sugaru::pipeline;
 
-#[derive(Debug, Clone, Eq, PartialEq, Hash, Ord, PartialOrd, Default)]
+#[cfg(feature = "nightly")]
+use std::alloc::AllocError;
+
+/// Thread-safe version of [`CowRc`]: mutating a shared `CowArc` clones its value first.
+///
+/// Comparing two `CowArc`s pointing to the same allocation returns true without comparing the values
+/// when `T: Eq` (as [`Arc`] does), which is cheap for large values compared to their clones.
+/// Without `Eq`, values are always compared, since a value may differ from itself (NaN)
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Default)]
 pub struct CowArc<T: ?Sized> {
 	pub arc: Arc<T>,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Default)]
 pub struct WeakCowArc<T: ?Sized> {
 	pub weak: Weak<T>,
 }
@@ -20,6 +34,103 @@ impl<T> CowArc<T> {
 	pub fn new(value: T) -> Self {
 		pipeline!(value |> Arc::new |> Self::from_arc)
 	}
+
+	/// Constructs a new `CowArc<T>`, returning an error if the allocation fails
+	///
+	/// # Errors
+	///
+	/// Returns [`AllocError`] if the allocation fails
+	#[cfg(feature = "nightly")]
+	pub fn try_new(value: T) -> Result<Self, AllocError> {
+		Arc::try_new(value).map(Self::from_arc)
+	}
+
+	/// Points this `CowArc` to a new allocation of `value`, returning the previous pointer
+	/// (not the previous value, which would have to be cloned if shared)
+	#[must_use = "if the previous value is unused, assign a new `CowArc` instead"]
+	pub fn replace(this: &mut Self, value: T) -> Self {
+		mem::replace(this, Self::new(value))
+	}
+
+	/// Moves the value into a [`CowRc`] if this `CowArc` is the only strong pointer,
+	/// for values which no longer cross threads. Gives back the `CowArc` if it is shared
+	///
+	/// # Errors
+	///
+	/// Returns this `CowArc` unchanged if there are other strong pointers
+	pub fn try_into_rc(this: Self) -> Result<CowRc<T>, Self> {
+		Arc::try_unwrap(this.arc)
+			.map(CowRc::new)
+			.map_err(Self::from_arc)
+	}
+}
+
+impl<T: Clone> CowArc<T> {
+	/// Same as [`CowArc::unwrap_or_clone`]. Replaces `Arc::into_inner(..).unwrap_or_default()`,
+	/// which builds a default value and loses the shared one when this `CowArc` isn't unique
+	#[must_use]
+	pub fn unwrap_or_default(this: Self) -> T
+	where
+		T: Default,
+	{
+		Self::unwrap_or_clone(this)
+	}
+
+	/// Returns the inner value, moving it out if this `CowArc` is the only strong pointer,
+	/// cloning it otherwise. See [`Arc::unwrap_or_clone`]
+	#[must_use]
+	pub fn unwrap_or_clone(this: Self) -> T {
+		pipeline!(this.arc => Arc::unwrap_or_clone)
+	}
+
+	/// Applies a fallible transformation to the inner value,
+	/// moved out if this `CowArc` is the only strong pointer, cloned otherwise
+	///
+	/// # Errors
+	///
+	/// Returns the error of `f`
+	pub fn try_map<U, E>(this: Self, f: impl FnOnce(T) -> Result<U, E>) -> Result<CowArc<U>, E> {
+		f(Self::unwrap_or_clone(this)).map(CowArc::new)
+	}
+
+	/// Locks the shared `slot` and waits until its `CowArc` is the only strong pointer,
+	/// so that mutating it through the returned guard never clones.
+	/// For values too expensive to clone: the writer blocks until the readers are done.
+	///
+	/// Readers clone the `CowArc` out of the mutex, and must give their clone back
+	/// with [`CowArc::release`] to wake the writer up
+	pub fn make_mut_or_wait(slot: &(Mutex<Self>, Condvar)) -> MutexGuard<'_, Self> {
+		let (mutex, released) = slot;
+		let guard = mutex.lock().unwrap_or_else(PoisonError::into_inner);
+		released
+			.wait_while(guard, |cow_arc| Self::needs_cloning_to_mutate(cow_arc))
+			.unwrap_or_else(PoisonError::into_inner)
+	}
+
+	/// Drops a reader's `clone` of the `CowArc` in `slot` and wakes up the writers waiting in
+	/// [`CowArc::make_mut_or_wait`]. Both happen while holding the lock, otherwise a writer could
+	/// check the strong count just before the drop, and miss the notification sent before it waits
+	pub fn release(slot: &(Mutex<Self>, Condvar), clone: Self) {
+		let (mutex, released) = slot;
+		let _guard = mutex.lock().unwrap_or_else(PoisonError::into_inner);
+		drop(clone);
+		released.notify_all();
+	}
+
+	/// Applies an in-place edit, cloning the value first if this `CowArc` is shared,
+	/// and returns `self` to chain edits (only the first one may clone).
+	/// A method rather than an associated function, for chaining
+	pub fn modify(&mut self, f: impl FnOnce(&mut T)) -> &mut Self {
+		f(Self::make_mut(self));
+		self
+	}
+
+	/// Swaps the values pointed to by `a` and `b`, cloning them first if they are shared.
+	///
+	/// Unlike [`CowArc::swap`], unique values are swapped in place, keeping their addresses
+	pub fn swap_contents(a: &mut Self, b: &mut Self) {
+		mem::swap(Self::make_mut(a), Self::make_mut(b));
+	}
 }
 
 impl<T: ?Sized> CowArc<T> {
@@ -27,6 +138,12 @@ impl<T: ?Sized> CowArc<T> {
 		Self { arc }
 	}
 
+	/// Borrows the wrapped [`Arc`], to feed APIs taking a `&Arc<T>` without moving nor cloning it
+	#[must_use]
+	pub const fn as_arc(this: &Self) -> &Arc<T> {
+		&this.arc
+	}
+
 	#[inline]
 	#[must_use]
 	pub fn needs_cloning_to_mutate(this: &Self) -> bool {
@@ -39,12 +156,169 @@ impl<T: ?Sized> CowArc<T> {
 		!Self::needs_cloning_to_mutate(this) && Arc::weak_count(&this.arc) == 0
 	}
 
+	/// Returns true if both `CowArc` point to the same allocation, see [`Arc::ptr_eq`]
+	#[inline]
+	#[must_use]
+	pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+		Arc::ptr_eq(&this.arc, &other.arc)
+	}
+
+	/// Orders by allocation address, a total order on identities which doesn't need `T: Ord`
+	/// (e.g. to key a `BTreeMap` on graph nodes). Consistent with [`CowArc::ptr_eq`]
+	#[must_use]
+	pub fn cmp_by_ptr(this: &Self, other: &Self) -> Ordering {
+		Arc::as_ptr(&this.arc)
+			.cast::<()>()
+			.cmp(&Arc::as_ptr(&other.arc).cast())
+	}
+
+	/// Classifies the sharing state of this `CowArc`, combining
+	/// [`needs_cloning_to_mutate`](Self::needs_cloning_to_mutate) and [`is_unique`](Self::is_unique)
+	#[must_use]
+	pub fn sharing(this: &Self) -> Sharing {
+		if Self::needs_cloning_to_mutate(this) {
+			Sharing::Shared
+		} else if Self::is_unique(this) {
+			Sharing::Unique
+		} else {
+			Sharing::Observed
+		}
+	}
+
+	/// Panics in debug builds if this `CowArc` is not unique.
+	///
+	/// Put it before a batch of mutations expected not to clone, to catch accidental sharing
+	#[inline]
+	#[track_caller]
+	pub fn assert_unique(this: &Self) {
+		debug_assert!(
+			Self::is_unique(this),
+			"CowArc<{}> is not unique: mutating it would clone (strong count: {}, weak count: {})",
+			std::any::type_name::<T>(),
+			Arc::strong_count(&this.arc),
+			Arc::weak_count(&this.arc),
+		);
+	}
+
+	/// Swaps the pointers of `a` and `b`, without cloning nor moving the values
+	/// (same as [`mem::swap`] on the `CowArc`s)
+	pub const fn swap(a: &mut Self, b: &mut Self) {
+		mem::swap(&mut a.arc, &mut b.arc);
+	}
+
+	/// Makes this `CowArc` read-only, see [`Frozen`]
+	#[must_use]
+	pub const fn freeze(this: Self) -> Frozen<Self> {
+		Frozen::new(this)
+	}
+
+	/// Runs `f` on the inner value and returns this `CowArc`, like [`Iterator::inspect`]
+	#[must_use]
+	pub fn inspect(this: Self, f: impl FnOnce(&T)) -> Self {
+		f(&this);
+		this
+	}
+
+	/// Reads the inner value through `f`
+	pub fn map_ref<R>(this: &Self, f: impl FnOnce(&T) -> R) -> R {
+		f(this)
+	}
+
+	/// Mutably borrows the inner value if this `CowArc` is unique (see [`Arc::get_mut`]),
+	/// retrying up to `max_spins` times while another thread briefly holds a pointer.
+	/// Returns `None` if it is still shared afterwards, where [`CowArc::make_mut`] would clone.
+	/// Also returns `None` while any [`WeakCowArc`] exists, even without other strong pointers
+	/// (where `make_mut` would move the value away from the weak pointers instead of cloning)
+	pub fn get_mut_spin(this: &mut Self, max_spins: u32) -> Option<&mut T> {
+		let mut spins = 0;
+		// get_mut is called again to return, the borrow checker rejects returning it from the loop
+		while Arc::get_mut(&mut this.arc).is_none() {
+			if spins == max_spins {
+				return None;
+			}
+			spins += 1;
+			hint::spin_loop();
+		}
+		Arc::get_mut(&mut this.arc)
+	}
+
+	/// Mutably borrows the inner value, cloning it first if this `CowArc` is shared.
+	/// Same as dereferencing mutably, but never checked by the `strict-deref-mut` feature
+	pub fn make_mut(this: &mut Self) -> &mut T
+	where
+		T: Clone,
+	{
+		pipeline!(&mut this.arc => Arc::make_mut)
+	}
+
+	/// Mutably borrows the inner value for the duration of `f`, cloning it first if this `CowArc` is shared.
+	/// With the `debug-alias` feature, debug builds report that clone on stderr
+	pub fn with_alias_check<R>(this: &mut Self, f: impl FnOnce(&mut T) -> R) -> R
+	where
+		T: Clone,
+	{
+		#[cfg(feature = "debug-alias")]
+		Self::report_alias(this);
+		f(Self::make_mut(this))
+	}
+
+	#[cfg(feature = "debug-alias")]
+	fn report_alias(this: &Self) {
+		if Self::needs_cloning_to_mutate(this) {
+			crate::alias::report_clone::<T>("CowArc");
+		}
+	}
+
 	#[must_use]
 	pub fn downgrade(this: &Self) -> WeakCowArc<T> {
 		pipeline!(&this.arc => Arc::downgrade => WeakCowArc::from_weak)
 	}
+
+	/// Returns a new strong pointer along with a weak one, both to this allocation,
+	/// e.g. to keep ownership while registering an observer
+	#[must_use]
+	pub fn keep_weak(this: &Self) -> (Self, WeakCowArc<T>) {
+		(this.clone(), Self::downgrade(this))
+	}
+
+	/// Consumes the `CowArc` and returns a reference with a `'static` lifetime to its value.
+	///
+	/// The strong count is never decremented, so the value is intentionally leaked:
+	/// it is never dropped and the allocation lives until the end of the program.
+	/// Useful for immutable global tables built once at startup
+	#[must_use]
+	pub fn leak(this: Self) -> &'static T
+	where
+		T: 'static,
+	{
+		unsafe { &*Arc::into_raw(this.arc) }
+	}
+}
+
+impl<T: ?Sized> Clone for CowArc<T> {
+	/// Makes a clone of the `CowArc` pointer, without cloning the inner value
+	/// (derived `Clone` would require `T: Clone`, excluding `str` and `[T]`)
+	fn clone(&self) -> Self {
+		pipeline!(&self.arc => Arc::clone => Self::from_arc)
+	}
+}
+
+/// Forwards a binary operator to the inner values.
+/// Operands are moved out when unique and only cloned when shared
+macro_rules! forward_binary_operator {
+	($($Operator:ident::$operation:ident),+) => {$(
+		impl<T: Clone + $Operator<Output = T>> $Operator for CowArc<T> {
+			type Output = Self;
+
+			fn $operation(self, rhs: Self) -> Self::Output {
+				pipeline!(Self::unwrap_or_clone(self).$operation(Self::unwrap_or_clone(rhs)) => Self::new)
+			}
+		}
+	)+};
 }
 
+forward_binary_operator!(Add::add, Sub::sub, Mul::mul, Div::div, Rem::rem);
+
 impl<T, U> From<T> for CowArc<U>
 where
 	U: ?Sized,
@@ -67,6 +341,99 @@ impl<T: ?Sized + Clone> AsMut<T> for CowArc<T> {
 	}
 }
 
+// `From<CowArc<T>> for Arc<T>` would make `Arc<U>: From<CowArc<U>>` hold,
+// so the generic `From` above would overlap with the reflexive `From<T> for T`
+#[allow(clippy::from_over_into)]
+impl<T: ?Sized> Into<Arc<T>> for CowArc<T> {
+	/// Moves the wrapped `Arc` out, without touching the reference counts
+	fn into(self) -> Arc<T> {
+		self.arc
+	}
+}
+
+impl<T: ?Sized + Hash> Hash for CowArc<T> {
+	/// Hashes the inner value, so a `CowArc<T>` hashes like its `T`
+	/// (required for lookups through [`Borrow<T>`]).
+	/// `T` may be a trait object, given an `impl Hash for dyn Trait`
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		(**self).hash(state);
+	}
+}
+
+/// Compares the inner values, to mix `CowRc` and `CowArc` while migrating from one to the other
+impl<T: ?Sized + PartialEq> PartialEq<CowRc<T>> for CowArc<T> {
+	fn eq(&self, other: &CowRc<T>) -> bool {
+		**self == **other
+	}
+}
+
+impl<T: ?Sized + PartialEq> PartialEq<CowArc<T>> for CowRc<T> {
+	fn eq(&self, other: &CowArc<T>) -> bool {
+		**self == **other
+	}
+}
+
+/// Compares the inner values, consistently with the cross-type [`PartialEq`]
+impl<T: ?Sized + PartialOrd> PartialOrd<CowRc<T>> for CowArc<T> {
+	fn partial_cmp(&self, other: &CowRc<T>) -> Option<Ordering> {
+		(**self).partial_cmp(&**other)
+	}
+}
+
+impl<T: ?Sized + PartialOrd> PartialOrd<CowArc<T>> for CowRc<T> {
+	fn partial_cmp(&self, other: &CowArc<T>) -> Option<Ordering> {
+		(**self).partial_cmp(&**other)
+	}
+}
+
+/// Moves the value into a `CowArc` if the `CowRc` is the only strong pointer, clones it otherwise
+impl<T: Clone> From<CowRc<T>> for CowArc<T> {
+	fn from(value: CowRc<T>) -> Self {
+		pipeline!(value |> CowRc::unwrap_or_clone |> Self::new)
+	}
+}
+
+/// Moves the value into a `CowRc` if the `CowArc` is the only strong pointer, clones it otherwise
+impl<T: Clone> From<CowArc<T>> for CowRc<T> {
+	fn from(value: CowArc<T>) -> Self {
+		pipeline!(value |> CowArc::unwrap_or_clone |> Self::new)
+	}
+}
+
+/// Copies the string: the reference counts are stored next to it, in a different layout
+impl From<CowRc<str>> for CowArc<str> {
+	fn from(value: CowRc<str>) -> Self {
+		pipeline!(&*value |> Arc::from |> Self::from_arc)
+	}
+}
+
+impl From<CowArc<str>> for CowRc<str> {
+	fn from(value: CowArc<str>) -> Self {
+		pipeline!(&*value |> Rc::from |> Self::from_rc)
+	}
+}
+
+/// Clones the elements: the reference counts are stored next to them, in a different layout
+impl<T: Clone> From<CowRc<[T]>> for CowArc<[T]> {
+	fn from(value: CowRc<[T]>) -> Self {
+		pipeline!(&*value |> Arc::from |> Self::from_arc)
+	}
+}
+
+impl<T: Clone> From<CowArc<[T]>> for CowRc<[T]> {
+	fn from(value: CowArc<[T]>) -> Self {
+		pipeline!(&*value |> Rc::from |> Self::from_rc)
+	}
+}
+
+impl<T: ?Sized> From<&Self> for CowArc<T> {
+	/// Clones the `CowArc` pointer (without cloning the inner value),
+	/// for generic code bounded on `From<&CowArc<T>>`
+	fn from(value: &Self) -> Self {
+		value.clone()
+	}
+}
+
 impl<T: ?Sized> Deref for CowArc<T> {
 	type Target = T;
 
@@ -76,8 +443,95 @@ impl<T: ?Sized> Deref for CowArc<T> {
 }
 
 impl<T: ?Sized + Clone> DerefMut for CowArc<T> {
+	/// Clones the inner value if this `CowArc` is shared, like [`CowRc`'s `DerefMut`](CowRc::<T>::deref_mut).
+	/// With the `strict-deref-mut` feature, debug builds panic instead of cloning
 	fn deref_mut(&mut self) -> &mut Self::Target {
-		pipeline!(&mut self.arc => Arc::make_mut)
+		#[cfg(feature = "strict-deref-mut")]
+		debug_assert!(
+			!Self::needs_cloning_to_mutate(self),
+			"dereferencing a shared CowArc<{}> mutably would clone it, use CowArc::make_mut to clone explicitly",
+			std::any::type_name::<T>(),
+		);
+		#[cfg(feature = "debug-alias")]
+		Self::report_alias(self);
+		Self::make_mut(self)
+	}
+}
+
+impl<T: ?Sized> Borrow<T> for CowArc<T> {
+	fn borrow(&self) -> &T {
+		self
+	}
+}
+
+impl<T: Clone> BorrowMut<T> for CowArc<T> {
+	/// Mutably borrows the inner value. Has the same effect as dereferencing it,
+	/// so it clones the inner value if this `CowArc` is shared.
+	///
+	/// See [`DerefMut` implementation for `CowArc`](CowArc::<T>::deref_mut) for details
+	fn borrow_mut(&mut self) -> &mut T {
+		self
+	}
+}
+
+impl<T: Clone> CowArc<Vec<T>> {
+	/// Adds `scalar` to every element of the `Vec`, cloning it first if it is shared
+	/// (the clone happens once, not per element)
+	#[allow(clippy::needless_pass_by_value)]
+	pub fn add_scalar(this: &mut Self, scalar: T)
+	where
+		T: AddAssign,
+	{
+		for element in Self::make_mut(this) {
+			*element += scalar.clone();
+		}
+	}
+}
+
+impl io::Write for CowArc<Vec<u8>> {
+	/// Appends `buf` to the inner `Vec`, cloning it first if this `CowArc` is shared
+	fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+		pipeline!(&mut self.arc => Arc::make_mut).extend_from_slice(buf);
+		Ok(buf.len())
+	}
+
+	fn flush(&mut self) -> io::Result<()> {
+		Ok(())
+	}
+}
+
+/// Fluent construction of a [`CowArc`], like [`ToOwned::to_owned`] or [`ToString::to_string`]
+///
+/// ```
+/// use optimistic_mutation::sync::{CowArc, ToCowArc};
+///
+/// let number: CowArc<i32> = 5.to_cow_arc();
+/// let str: CowArc<str> = "Hello".to_cow_arc();
+/// let slice: CowArc<[i32]> = [1, 2][..].to_cow_arc();
+///
+/// assert_eq!(*number, 5);
+/// assert_eq!(&*str, "Hello");
+/// assert_eq!(&*slice, [1, 2]);
+/// ```
+pub trait ToCowArc {
+	fn to_cow_arc(&self) -> CowArc<Self>;
+}
+
+impl<T: Clone> ToCowArc for T {
+	fn to_cow_arc(&self) -> CowArc<Self> {
+		pipeline!(self |> Self::clone |> CowArc::new)
+	}
+}
+
+impl ToCowArc for str {
+	fn to_cow_arc(&self) -> CowArc<Self> {
+		CowArc::from(self)
+	}
+}
+
+impl<T: Clone> ToCowArc for [T] {
+	fn to_cow_arc(&self) -> CowArc<Self> {
+		CowArc::from(self)
 	}
 }
 
@@ -86,6 +540,16 @@ impl<T> WeakCowArc<T> {
 	pub const fn new() -> Self {
 		pipeline!(Weak::new() => Self::from_weak)
 	}
+
+	/// Upgrades `weak` if its value is alive, otherwise builds a new value with `f`
+	/// and points `weak` to it: a lazily (re)built value shared while someone holds it
+	pub fn upgrade_or_insert_with(weak: &mut Self, f: impl FnOnce() -> T) -> CowArc<T> {
+		Self::upgrade(weak).unwrap_or_else(|| {
+			let strong = CowArc::new(f());
+			*weak = CowArc::downgrade(&strong);
+			strong
+		})
+	}
 }
 
 impl<T: ?Sized> WeakCowArc<T> {
@@ -100,9 +564,187 @@ impl<T: ?Sized> WeakCowArc<T> {
 	}
 }
 
+impl<T: ?Sized + Display> Display for WeakCowArc<T> {
+	/// Displays the value if it is still alive, `<dropped>` otherwise
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		match Self::upgrade(self) {
+			Some(value) => Display::fmt(&*value, f),
+			None => f.write_str("<dropped>"),
+		}
+	}
+}
+
+impl<T: ?Sized> Clone for WeakCowArc<T> {
+	fn clone(&self) -> Self {
+		pipeline!(&self.weak => Weak::clone => Self::from_weak)
+	}
+}
+
+/// A string that is either a compile-time constant or a shared runtime string.
+///
+/// `Arc<str>` always owns its data: wrapping a `&'static str` in a `CowArc<str>` would allocate.
+/// This avoids allocating for constants while still allowing shared runtime strings
+#[derive(Debug, Clone)]
+pub enum StaticOrShared {
+	Static(&'static str),
+	Shared(CowArc<str>),
+}
+
+impl StaticOrShared {
+	#[must_use]
+	pub const fn from_static(string_slice: &'static str) -> Self {
+		Self::Static(string_slice)
+	}
+}
+
+impl Deref for StaticOrShared {
+	type Target = str;
+
+	fn deref(&self) -> &Self::Target {
+		match self {
+			Self::Static(string_slice) => string_slice,
+			Self::Shared(cow_arc) => cow_arc,
+		}
+	}
+}
+
+impl AsRef<str> for StaticOrShared {
+	fn as_ref(&self) -> &str {
+		self
+	}
+}
+
+impl Borrow<str> for StaticOrShared {
+	fn borrow(&self) -> &str {
+		self
+	}
+}
+
+impl PartialEq for StaticOrShared {
+	/// Compares the strings, whatever the variants
+	fn eq(&self, other: &Self) -> bool {
+		**self == **other
+	}
+}
+
+impl Eq for StaticOrShared {}
+
+impl Hash for StaticOrShared {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		(**self).hash(state);
+	}
+}
+
+impl Display for StaticOrShared {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		Display::fmt(&**self, f)
+	}
+}
+
+impl From<&'static str> for StaticOrShared {
+	fn from(value: &'static str) -> Self {
+		Self::Static(value)
+	}
+}
+
+impl From<CowArc<str>> for StaticOrShared {
+	fn from(value: CowArc<str>) -> Self {
+		Self::Shared(value)
+	}
+}
+
+/// A shared `CowArc` slot supporting optimistic concurrency.
+///
+/// Readers [`load`](Self::load) a snapshot, compute a new value from it,
+/// and [`compare_and_swap`](Self::compare_and_swap) it in, retrying if another writer came first.
+///
+/// The lock is only held to clone or swap the pointer, never while computing
+#[derive(Debug, Default)]
+pub struct CasCell<T: ?Sized> {
+	slot: Mutex<CowArc<T>>,
+}
+
+impl<T: ?Sized> CasCell<T> {
+	#[must_use]
+	pub const fn new(value: CowArc<T>) -> Self {
+		Self {
+			slot: Mutex::new(value),
+		}
+	}
+
+	/// Returns a snapshot of the current value (cloning the pointer only)
+	#[must_use]
+	pub fn load(&self) -> CowArc<T> {
+		self.lock().clone()
+	}
+
+	/// Replaces the current value, returning the previous one
+	pub fn swap(&self, new: CowArc<T>) -> CowArc<T> {
+		mem::replace(&mut self.lock(), new)
+	}
+
+	/// Stores `new` if the current value is still the allocation of `expected`
+	/// (compared with [`CowArc::ptr_eq`], not by value).
+	/// Returns false, dropping `new`, if another writer replaced it in between
+	pub fn compare_and_swap(&self, expected: &CowArc<T>, new: CowArc<T>) -> bool {
+		let mut current = self.lock();
+		let swapped = CowArc::ptr_eq(&current, expected);
+		if swapped {
+			*current = new;
+		}
+		swapped
+	}
+
+	/// Swapping a pointer can't leave the slot inconsistent, so poisoning is ignored
+	fn lock(&self) -> MutexGuard<'_, CowArc<T>> {
+		self.slot.lock().unwrap_or_else(PoisonError::into_inner)
+	}
+}
+
+/// A shared `CowArc` slot for read-copy-update, with an API in the style of `arc-swap`.
+///
+/// Built on a [`CasCell`] rather than an atomic pointer, to avoid dependencies and `unsafe`
+#[derive(Debug, Default)]
+pub struct AtomicCowArc<T: ?Sized> {
+	cell: CasCell<T>,
+}
+
+impl<T: ?Sized> AtomicCowArc<T> {
+	#[must_use]
+	pub const fn new(value: CowArc<T>) -> Self {
+		Self {
+			cell: CasCell::new(value),
+		}
+	}
+
+	/// Returns a snapshot of the current value (cloning the pointer only)
+	#[must_use]
+	pub fn load(&self) -> CowArc<T> {
+		self.cell.load()
+	}
+
+	pub fn store(&self, value: CowArc<T>) {
+		self.cell.swap(value);
+	}
+
+	/// Replaces the current value with `f` applied to it, returning the previous value.
+	///
+	/// `f` runs without holding the lock, and is called again with the new current value
+	/// if another writer replaced it in between, so no update is lost
+	pub fn rcu(&self, f: impl Fn(&CowArc<T>) -> CowArc<T>) -> CowArc<T> {
+		loop {
+			let current = self.load();
+			if self.cell.compare_and_swap(&current, f(&current)) {
+				return current;
+			}
+		}
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use std::ptr;
 
 	#[derive(Debug, Clone)]
 	struct Person {
@@ -116,6 +758,7 @@ mod tests {
 	}
 
 	#[test]
+	#[cfg_attr(feature = "strict-deref-mut", ignore = "clones through DerefMut")]
 	fn sandbox() {
 		let person1 = Person {
 			purse: CowArc::new(Purse { nb_of_keys: 4 }),
@@ -131,4 +774,605 @@ mod tests {
 		assert_eq!(person1.purse.nb_of_keys, 4); // Original person is unaffected
 		assert_eq!(person2.purse.nb_of_keys, 3);
 	}
+
+	#[test]
+	#[cfg_attr(feature = "strict-deref-mut", ignore = "clones through DerefMut")]
+	fn borrow_mut() {
+		fn increment<B: BorrowMut<i32>>(mut borrowed: B) -> B {
+			*borrowed.borrow_mut() += 1;
+			borrowed
+		}
+
+		let unique = CowArc::new(1);
+		let unique_ptr = Arc::as_ptr(&unique.arc);
+		let unique = increment(unique);
+		assert_eq!(*unique, 2);
+		assert_eq!(Arc::as_ptr(&unique.arc), unique_ptr); // No clone
+
+		let shared = CowArc::new(1);
+		let incremented = increment(shared.clone());
+		assert_eq!(*incremented, 2);
+		assert_eq!(*shared, 1);
+	}
+
+	#[test]
+	fn arithmetic() {
+		let six: CowArc<i64> = CowArc::new(2) * CowArc::new(3);
+		let shared = CowArc::new(4);
+		assert_eq!(*(six.clone() + shared.clone()), 10);
+		assert_eq!(*(six.clone() - shared.clone()), 2);
+		assert_eq!(*(CowArc::new(9) / shared.clone()), 2);
+		assert_eq!(*(CowArc::new(9) % shared.clone()), 1);
+		assert_eq!((*six, *shared), (6, 4));
+	}
+
+	#[test]
+	fn arithmetic_moves_unique_operands() {
+		#[derive(Debug, PartialEq)]
+		struct TrapClone(i64);
+
+		impl Clone for TrapClone {
+			fn clone(&self) -> Self {
+				panic!("Test failed: clone was called")
+			}
+		}
+
+		impl std::ops::Add for TrapClone {
+			type Output = Self;
+
+			fn add(self, rhs: Self) -> Self {
+				Self(self.0 + rhs.0)
+			}
+		}
+
+		let sum = CowArc::new(TrapClone(1)) + CowArc::new(TrapClone(2));
+		assert_eq!(*sum, TrapClone(3));
+	}
+
+	#[test]
+	fn leak() {
+		let table: &'static [u8] = CowArc::leak(CowArc::from([1, 2, 3]));
+		assert_eq!(table, [1, 2, 3]);
+
+		let value = CowArc::new(5);
+		let clone = value.clone();
+		let leaked: &'static i32 = CowArc::leak(value);
+		drop(clone);
+		assert_eq!(*leaked, 5);
+	}
+
+	#[test]
+	#[cfg(feature = "nightly")]
+	fn try_new() {
+		let five = CowArc::try_new(5).unwrap();
+		assert_eq!(*five, 5);
+		assert!(CowArc::is_unique(&five));
+	}
+
+	#[test]
+	fn assert_unique() {
+		let unique = CowArc::new(1);
+		CowArc::assert_unique(&unique);
+	}
+
+	#[test]
+	#[cfg(debug_assertions)]
+	#[should_panic(expected = "CowArc<i32> is not unique")]
+	fn assert_unique_panics_when_shared() {
+		let shared = CowArc::new(1);
+		let _clone = shared.clone();
+		CowArc::assert_unique(&shared);
+	}
+
+	#[test]
+	fn static_or_shared() {
+		const GREETING: StaticOrShared = StaticOrShared::from_static("Hello");
+		let shared = StaticOrShared::from(CowArc::<str>::from("Hello"));
+
+		assert!(matches!(GREETING, StaticOrShared::Static(_)));
+		assert!(matches!(shared, StaticOrShared::Shared(_)));
+		assert_eq!(&*GREETING, &*shared);
+		assert_eq!(GREETING, shared);
+		assert_eq!(shared.len(), 5);
+		assert_eq!(shared.to_string(), "Hello");
+	}
+
+	#[test]
+	fn io_write() {
+		use std::io::Write;
+
+		let original = CowArc::new(b"Hello".to_vec());
+		let mut buffer = original.clone();
+		let name = "world";
+		write!(buffer, ", {name}!").unwrap();
+		buffer.write_all(b"!").unwrap();
+		buffer.flush().unwrap();
+
+		assert_eq!(&**buffer, b"Hello, world!!");
+		assert_eq!(&**original, b"Hello");
+	}
+
+	#[test]
+	fn from_reference() {
+		fn convert<C: for<'a> From<&'a CowArc<str>>>(cow: &CowArc<str>) -> C {
+			C::from(cow)
+		}
+
+		let original: CowArc<str> = CowArc::from("Hello");
+		let converted: CowArc<str> = convert(&original);
+		assert_eq!(Arc::as_ptr(&original.arc), Arc::as_ptr(&converted.arc));
+		assert_eq!(Arc::strong_count(&original.arc), 2);
+	}
+
+	#[test]
+	fn sharing() {
+		use crate::sharing::Sharing;
+
+		let value = CowArc::new(1);
+		assert_eq!(CowArc::sharing(&value), Sharing::Unique);
+		let weak = CowArc::downgrade(&value);
+		assert_eq!(CowArc::sharing(&value), Sharing::Observed);
+		let clone = value.clone();
+		assert_eq!(CowArc::sharing(&value), Sharing::Shared);
+		drop((weak, clone));
+		assert_eq!(CowArc::sharing(&value), Sharing::Unique);
+	}
+
+	#[test]
+	fn display_weak() {
+		let value = CowArc::new(5);
+		let weak = CowArc::downgrade(&value);
+		assert_eq!(weak.to_string(), "5");
+		drop(value);
+		assert_eq!(weak.to_string(), "<dropped>");
+	}
+
+	#[test]
+	fn hash() {
+		use std::hash::{BuildHasher, Hash, Hasher, RandomState};
+
+		trait Shape {
+			fn sides(&self) -> u32;
+		}
+
+		struct Square;
+
+		impl Shape for Square {
+			fn sides(&self) -> u32 {
+				4
+			}
+		}
+
+		impl Hash for dyn Shape {
+			fn hash<H: Hasher>(&self, state: &mut H) {
+				self.sides().hash(state);
+			}
+		}
+
+		let state = RandomState::new();
+		let str: CowArc<str> = CowArc::from("Hello");
+		assert_eq!(state.hash_one(&str), state.hash_one("Hello"));
+		assert_eq!(state.hash_one(CowArc::new(5)), state.hash_one(5));
+
+		let shape: CowArc<dyn Shape> = CowArc::from_arc(Arc::new(Square));
+		assert_eq!(state.hash_one(&shape), state.hash_one(4_u32));
+	}
+
+	#[test]
+	fn try_map() {
+		#[derive(Debug)]
+		struct TrapClone(String);
+
+		impl Clone for TrapClone {
+			fn clone(&self) -> Self {
+				panic!("Test failed: clone was called")
+			}
+		}
+
+		let text = CowArc::new(String::from("42"));
+		let number = CowArc::try_map(text.clone(), |text| text.parse::<i32>());
+		assert_eq!(number.map(|number| *number), Ok(42));
+		assert_eq!(*text, "42");
+
+		let invalid = CowArc::new(String::from("abc"));
+		assert!(CowArc::try_map(invalid, |text| text.parse::<i32>()).is_err());
+
+		let unique = CowArc::new(TrapClone(String::from("7")));
+		let parsed = CowArc::try_map(unique, |TrapClone(text)| text.parse::<u8>()).unwrap();
+		assert_eq!(*parsed, 7);
+	}
+
+	#[test]
+	fn cross_type_comparisons() {
+		let rc = CowRc::new(2);
+		let arc = CowArc::new(2);
+		assert_eq!(rc, arc);
+		assert_eq!(arc, rc);
+		assert!(CowArc::new(1) < rc);
+		assert!(CowRc::new(3) > arc);
+		assert_eq!(rc.partial_cmp(&arc), Some(Ordering::Equal));
+
+		let rc: CowRc<str> = CowRc::from("b");
+		let arc: CowArc<str> = CowArc::from("a");
+		assert!(arc < rc);
+		assert!(rc > arc);
+		assert_ne!(rc, arc);
+	}
+
+	#[test]
+	#[cfg(all(feature = "strict-deref-mut", debug_assertions))]
+	#[should_panic = "would clone"]
+	fn strict_deref_mut_shared() {
+		let mut data = CowArc::new(1);
+		let _clone = data.clone();
+		*data += 1;
+	}
+
+	#[test]
+	#[cfg(feature = "strict-deref-mut")]
+	fn strict_deref_mut_unique() {
+		let mut data = CowArc::new(1);
+		let weak = CowArc::downgrade(&data);
+		*data += 1; // Moves the value out of the weak pointers' reach, but doesn't clone
+		assert_eq!(*data, 2);
+		drop(weak);
+
+		let clone = data.clone();
+		*CowArc::make_mut(&mut data) += 1; // Explicit clone
+		assert_eq!((*data, *clone), (3, 2));
+	}
+
+	#[test]
+	fn swap() {
+		let mut a = CowArc::new(1);
+		let mut b = CowArc::new(2);
+		let weak = CowArc::downgrade(&a);
+
+		CowArc::swap(&mut a, &mut b);
+		assert_eq!((*a, *b), (2, 1));
+		assert_eq!(*WeakCowArc::upgrade(&weak).unwrap(), 1); // Follows the allocation, now in `b`
+		drop(weak);
+
+		let addresses = (ptr::from_ref(&*a), ptr::from_ref(&*b));
+		CowArc::swap_contents(&mut a, &mut b);
+		assert_eq!((*a, *b), (1, 2));
+		assert_eq!((ptr::from_ref(&*a), ptr::from_ref(&*b)), addresses); // Unique, swapped in place
+
+		let shared = b.clone();
+		CowArc::swap_contents(&mut a, &mut b);
+		assert_eq!((*a, *b, *shared), (2, 1, 2)); // `b` was cloned before the swap
+	}
+
+	#[test]
+	fn as_arc() {
+		fn shared_len(arc: &Arc<str>) -> (usize, usize) {
+			(arc.len(), Arc::strong_count(arc))
+		}
+
+		let hello: CowArc<str> = CowArc::from("Hello");
+		let clone = hello.clone();
+		assert_eq!(shared_len(CowArc::as_arc(&hello)), (5, 2));
+		drop(clone);
+		assert_eq!(shared_len(CowArc::as_arc(&hello)), (5, 1));
+	}
+
+	#[test]
+	fn unwrap_or_default() {
+		let unique = CowArc::new(String::from("unique"));
+		assert_eq!(CowArc::unwrap_or_default(unique), "unique");
+
+		let shared = CowArc::new(String::from("shared"));
+		let other = shared.clone();
+		assert_eq!(CowArc::unwrap_or_default(shared), "shared"); // Cloned
+		assert_eq!(*other, "shared"); // Still owned by `other`
+
+		let last = other.clone();
+		drop(other);
+		assert_eq!(CowArc::unwrap_or_default(last), "shared");
+	}
+
+	#[test]
+	fn try_into_rc() {
+		let unique = CowArc::new(String::from("unique"));
+		let address = unique.as_ptr();
+		let rc = CowArc::try_into_rc(unique).unwrap();
+		assert_eq!(*rc, "unique");
+		assert_eq!(rc.as_ptr(), address); // Moved, not cloned
+
+		let shared = CowArc::new(String::from("shared"));
+		let clone = shared.clone();
+		let shared = CowArc::try_into_rc(shared).unwrap_err();
+		assert!(CowArc::ptr_eq(&shared, &clone));
+	}
+
+	#[test]
+	fn compare_and_swap() {
+		let cell = CasCell::new(CowArc::new(0));
+		let stale = cell.load();
+		assert!(cell.compare_and_swap(&stale, CowArc::new(1)));
+		assert!(!cell.compare_and_swap(&stale, CowArc::new(2))); // Replaced in between
+		assert_eq!(*cell.load(), 1);
+
+		let cell = Arc::new(cell);
+		let threads: Vec<_> = (0..4)
+			.map(|_| {
+				let cell = Arc::clone(&cell);
+				std::thread::spawn(move || {
+					for _ in 0..100 {
+						loop {
+							let current = cell.load();
+							if cell.compare_and_swap(&current, CowArc::new(*current + 1)) {
+								break;
+							}
+						}
+					}
+				})
+			})
+			.collect();
+		for thread in threads {
+			thread.join().unwrap();
+		}
+		assert_eq!(*cell.load(), 401);
+		assert_eq!(*cell.swap(CowArc::new(0)), 401);
+	}
+
+	#[test]
+	fn rcu() {
+		let counters = Arc::new(AtomicCowArc::new(CowArc::new(vec![0; 4])));
+		let threads: Vec<_> = (0..4)
+			.map(|thread| {
+				let counters = Arc::clone(&counters);
+				std::thread::spawn(move || {
+					for _ in 0..100 {
+						counters.rcu(|current| {
+							let mut next = current.clone();
+							CowArc::make_mut(&mut next)[thread] += 1; // Clones the Vec: current is shared
+							next
+						});
+					}
+				})
+			})
+			.collect();
+		let snapshot = counters.load();
+		for thread in threads {
+			thread.join().unwrap();
+		}
+		assert_eq!(*counters.load(), [100; 4]);
+		assert!(snapshot.iter().all(|&count| count <= 100));
+
+		counters.store(CowArc::new(Vec::new()));
+		assert_eq!(*counters.rcu(|_| CowArc::new(vec![1])), []);
+		assert_eq!(*counters.load(), [1]);
+	}
+
+	#[test]
+	fn arc_conversions() {
+		let arc: Arc<str> = Arc::from("shared");
+		let cow: CowArc<str> = CowArc::from(Arc::clone(&arc));
+		assert_eq!(Arc::strong_count(&arc), 2);
+
+		let back: Arc<str> = cow.into();
+		assert_eq!(Arc::strong_count(&arc), 2); // Moved, not cloned
+		assert!(Arc::ptr_eq(&back, &arc));
+		drop(back);
+		assert_eq!(Arc::strong_count(&arc), 1);
+	}
+
+	#[test]
+	fn upgrade_or_insert_with() {
+		let mut cache = WeakCowArc::new();
+		let first = WeakCowArc::upgrade_or_insert_with(&mut cache, || 1);
+		let alive = WeakCowArc::upgrade_or_insert_with(&mut cache, || 2);
+		assert!(CowArc::ptr_eq(&first, &alive));
+		assert_eq!(*alive, 1);
+
+		drop((first, alive));
+		assert!(WeakCowArc::upgrade(&cache).is_none());
+		let rebuilt = WeakCowArc::upgrade_or_insert_with(&mut cache, || 3);
+		assert_eq!(*rebuilt, 3);
+		assert_eq!(*WeakCowArc::upgrade(&cache).unwrap(), 3);
+	}
+
+	#[test]
+	fn cmp_by_ptr() {
+		let a: CowArc<str> = CowArc::from("same");
+		let b: CowArc<str> = CowArc::from("same");
+		assert_eq!(
+			CowArc::cmp_by_ptr(&a, &a.clone()),
+			std::cmp::Ordering::Equal
+		);
+		assert_eq!(
+			CowArc::cmp_by_ptr(&a, &b),
+			CowArc::cmp_by_ptr(&b, &a).reverse()
+		);
+		assert_ne!(CowArc::cmp_by_ptr(&a, &b), std::cmp::Ordering::Equal); // Equal values, different identities
+
+		let mut nodes = vec![b.clone(), a.clone(), b, a];
+		nodes.sort_by(CowArc::cmp_by_ptr);
+		nodes.dedup_by(|x, y| CowArc::ptr_eq(x, y));
+		assert_eq!(nodes.len(), 2);
+	}
+
+	#[test]
+	fn modify() {
+		#[derive(Debug, Clone, PartialEq)]
+		struct Config {
+			name: String,
+			retries: u8,
+		}
+
+		let original = CowArc::new(Config {
+			name: "default".to_owned(),
+			retries: 1,
+		});
+		let mut config = original.clone();
+		let address = std::ptr::from_ref(&*original);
+		config
+			.modify(|config| config.name.push_str("-custom"))
+			.modify(|config| config.retries += 2);
+		let cloned = std::ptr::from_ref(&*config);
+		assert_ne!(cloned, address); // Cloned by the first edit
+		config.modify(|config| config.retries *= 2);
+		assert_eq!(std::ptr::from_ref(&*config), cloned); // Unique afterwards
+
+		assert_eq!(CowArc::map_ref(&config, |config| config.retries), 6);
+		assert_eq!(config.name, "default-custom");
+		assert_eq!(CowArc::map_ref(&original, |config| config.retries), 1);
+	}
+
+	#[test]
+	fn make_mut_or_wait() {
+		let slot = Arc::new((Mutex::new(CowArc::new(vec![1, 2])), Condvar::new()));
+		let address = ptr::from_ref(&**slot.0.lock().unwrap());
+		let (snapshot_taken, wait_for_snapshot) = std::sync::mpsc::channel();
+
+		let reader = {
+			let slot = Arc::clone(&slot);
+			std::thread::spawn(move || {
+				let snapshot = slot.0.lock().unwrap().clone();
+				snapshot_taken.send(()).unwrap();
+				std::thread::sleep(std::time::Duration::from_millis(50));
+				let sum: i32 = snapshot.iter().sum();
+				CowArc::release(&slot, snapshot);
+				sum
+			})
+		};
+
+		wait_for_snapshot.recv().unwrap();
+		let mut guard = CowArc::make_mut_or_wait(&slot);
+		guard.push(3);
+		assert_eq!(ptr::from_ref(&**guard), address); // The reader is done: no clone
+		drop(guard);
+		assert_eq!(reader.join().unwrap(), 3);
+		assert_eq!(**slot.0.lock().unwrap(), [1, 2, 3]);
+	}
+
+	#[test]
+	fn release() {
+		let slot = (Mutex::new(CowArc::new(1)), Condvar::new());
+		let clone = slot.0.lock().unwrap().clone();
+		CowArc::release(&slot, clone);
+		assert!(CowArc::is_unique(&slot.0.lock().unwrap()));
+		**CowArc::make_mut_or_wait(&slot) += 1; // Doesn't block: no reader left
+		assert_eq!(**slot.0.lock().unwrap(), 2);
+	}
+
+	#[test]
+	fn eq_short_circuits_on_identity() {
+		thread_local! {
+			static COMPARISONS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+		}
+
+		#[derive(Debug, Eq)]
+		struct Large(Vec<i32>);
+
+		impl PartialEq for Large {
+			fn eq(&self, other: &Self) -> bool {
+				COMPARISONS.set(COMPARISONS.get() + 1);
+				self.0 == other.0
+			}
+		}
+
+		let value = CowArc::new(Large(vec![1; 1000]));
+		assert_eq!(value, value.clone()); // Aliased
+		assert_eq!(COMPARISONS.get(), 0);
+		assert_eq!(value, CowArc::new(Large(vec![1; 1000]))); // Distinct but equal
+		assert_ne!(value, CowArc::new(Large(vec![2; 1000])));
+		assert_eq!(COMPARISONS.get(), 2);
+
+		let nan = CowArc::new(f64::NAN);
+		assert_ne!(nan, nan.clone()); // Not `Eq`: compared by value
+	}
+
+	#[test]
+	fn replace() {
+		let mut current = CowArc::new(String::from("old"));
+		let other = current.clone();
+		let previous = CowArc::replace(&mut current, String::from("new"));
+		assert_eq!((&**previous, &**current), ("old", "new"));
+		assert!(CowArc::ptr_eq(&previous, &other)); // Not cloned
+	}
+
+	#[test]
+	fn get_mut_spin() {
+		let mut value = CowArc::new(vec![1]);
+		let reader = value.clone();
+		assert!(CowArc::get_mut_spin(&mut value, 10).is_none());
+
+		let reader = std::thread::spawn(move || {
+			std::thread::sleep(std::time::Duration::from_millis(10));
+			reader.len()
+		});
+		let address = ptr::from_ref(&*value);
+		CowArc::get_mut_spin(&mut value, u32::MAX)
+			.expect("the reader drops its pointer")
+			.push(2);
+		assert_eq!(reader.join().unwrap(), 1);
+		assert_eq!(*value, [1, 2]);
+		assert_eq!(ptr::from_ref(&*value), address); // Not cloned
+
+		let weak = CowArc::downgrade(&value);
+		assert!(CowArc::get_mut_spin(&mut value, 10).is_none());
+		drop(weak);
+		assert!(CowArc::get_mut_spin(&mut value, 0).is_some());
+	}
+
+	#[test]
+	fn inspect() {
+		let mut seen = None;
+		let value = CowArc::inspect(CowArc::new(5), |value| seen = Some(*value));
+		assert_eq!(seen, Some(5));
+		assert_eq!(*value, 5);
+	}
+
+	#[test]
+	fn keep_weak() {
+		let original = CowArc::new(1);
+		let (strong, weak) = CowArc::keep_weak(&original);
+		let upgraded = WeakCowArc::upgrade(&weak).unwrap();
+		assert!(CowArc::ptr_eq(&upgraded, &original));
+		assert!(CowArc::ptr_eq(&upgraded, &strong));
+		assert_eq!(Arc::strong_count(&original.arc), 3);
+
+		drop((original, strong, upgraded));
+		assert!(WeakCowArc::upgrade(&weak).is_none());
+	}
+
+	#[test]
+	fn add_scalar() {
+		let mut unique = CowArc::new(vec![1, 2, 3]);
+		CowArc::add_scalar(&mut unique, 10);
+		assert_eq!(*unique, [11, 12, 13]);
+
+		let original = CowArc::new(vec![1.5, 2.5]);
+		let mut shared = original.clone();
+		CowArc::add_scalar(&mut shared, 1.0);
+		assert_eq!(*shared, [2.5, 3.5]);
+		assert_eq!(*original, [1.5, 2.5]);
+	}
+
+	#[test]
+	fn from_cow_rc() {
+		let unique = CowRc::new(String::from("unique"));
+		let address = unique.as_ptr();
+		let arc: CowArc<String> = CowArc::from(unique);
+		assert_eq!(*arc, "unique");
+		assert_eq!(arc.as_ptr(), address); // Moved, not cloned
+
+		let shared = CowRc::new(String::from("shared"));
+		let clone = shared.clone();
+		let arc: CowArc<String> = shared.into();
+		assert_eq!(*arc, *clone);
+		assert_ne!(arc.as_ptr(), clone.as_ptr());
+
+		let back: CowRc<String> = arc.into();
+		assert_eq!(*back, "shared");
+
+		let str: CowArc<str> = CowRc::<str>::from("str").into();
+		let str: CowRc<str> = str.into();
+		assert_eq!(&*str, "str");
+		let slice: CowArc<[i32]> = CowRc::<[i32]>::from([1, 2]).into();
+		let slice: CowRc<[i32]> = slice.into();
+		assert_eq!(*slice, [1, 2]);
+	}
 }
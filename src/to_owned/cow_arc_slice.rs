@@ -0,0 +1,317 @@
+use crate::sync::CowArc;
+use std::{
+	iter,
+	mem::{self, ManuallyDrop},
+	ops::{Deref, Range},
+	ptr,
+	sync::Arc,
+};
+use sugaru::pipeline;
+
+impl<T: Clone> From<CowArc<[T]>> for Vec<T> {
+	fn from(value: CowArc<[T]>) -> Self {
+		CowArc::into_vec(value)
+	}
+}
+
+impl<T: Clone, const N: usize> TryFrom<CowArc<[T]>> for [T; N] {
+	type Error = CowArc<[T]>;
+
+	/// Moves the elements out of the slice when the `CowArc` is unique,
+	/// clones them otherwise.
+	/// Gives back the `CowArc` untouched if its length is not `N`
+	fn try_from(value: CowArc<[T]>) -> Result<Self, Self::Error> {
+		if value.len() == N {
+			pipeline!(value => CowArc::into_vec => Self::try_from).map_err(CowArc::from)
+		} else {
+			Err(value)
+		}
+	}
+}
+
+impl<T: Clone> CowArc<[T]> {
+	/// Copies a slice into a new allocation. Unlike `CowArc::from`, it takes `&Vec<T>`
+	/// through deref coercion: a `From<&Vec<T>>` impl would conflict with the generic `From`
+	#[must_use]
+	pub fn from_slice(slice: &[T]) -> Self {
+		Self::from(slice)
+	}
+
+	/// Returns a clone of the element at `index`, or `None` if out of bounds,
+	/// without borrowing the shared slice
+	#[must_use]
+	pub fn get_cloned(&self, index: usize) -> Option<T> {
+		self.get(index).cloned()
+	}
+
+	/// Sets every element to `value`: in place if this `CowArc` is unique,
+	/// in a new allocation otherwise (without cloning the previous elements)
+	pub fn fill(this: &mut Self, value: T) {
+		if Self::needs_cloning_to_mutate(this) {
+			*this = iter::repeat_n(value, this.len())
+				.collect::<Arc<[T]>>()
+				.into();
+		} else {
+			Arc::make_mut(&mut this.arc).fill(value);
+		}
+	}
+
+	/// Sets every element to the values returned by `f`, see [`CowArc::fill`]
+	pub fn fill_with(this: &mut Self, f: impl FnMut() -> T) {
+		if Self::needs_cloning_to_mutate(this) {
+			*this = iter::repeat_with(f)
+				.take(this.len())
+				.collect::<Arc<[T]>>()
+				.into();
+		} else {
+			Arc::make_mut(&mut this.arc).fill_with(f);
+		}
+	}
+
+	/// Rotates the elements in place, cloning them first if this `CowArc` is shared.
+	/// See [`slice::rotate_left`]
+	///
+	/// # Panics
+	///
+	/// Panics if `mid` is greater than the length
+	pub fn rotate_left(this: &mut Self, mid: usize) {
+		Arc::make_mut(&mut this.arc).rotate_left(mid);
+	}
+
+	/// Rotates the elements in place, cloning them first if this `CowArc` is shared.
+	/// See [`slice::rotate_right`]
+	///
+	/// # Panics
+	///
+	/// Panics if `k` is greater than the length
+	pub fn rotate_right(this: &mut Self, k: usize) {
+		Arc::make_mut(&mut this.arc).rotate_right(k);
+	}
+
+	/// Converts this slice into a [`Vec`].
+	/// The elements are moved out if this `CowArc` is unique, cloned otherwise.
+	/// Unlike the `Rc` version, a weak pointer forces cloning,
+	/// since it could be upgraded concurrently by another thread
+	#[must_use]
+	pub fn into_vec(mut this: Self) -> Vec<T> {
+		if Arc::get_mut(&mut this.arc).is_none() {
+			return this.to_vec();
+		}
+		let len = this.len();
+		let mut vec = Vec::with_capacity(len);
+		unsafe {
+			ptr::copy_nonoverlapping(this.as_ptr(), vec.as_mut_ptr(), len);
+			vec.set_len(len);
+			// The elements now belong to vec, the allocation must be freed without dropping them
+			drop(Arc::from_raw(
+				Arc::into_raw(this.arc) as *const [ManuallyDrop<T>]
+			));
+		}
+		vec
+	}
+
+	/// Inserts `value` at its sorted position, returning its index.
+	/// The slice must already be sorted.
+	/// Cloning only occurs if this `CowArc` is shared
+	pub fn insert_sorted(this: &mut Self, value: T) -> usize
+	where
+		T: Ord,
+	{
+		let (Ok(index) | Err(index)) = this.binary_search(&value);
+		Self::with_vec(this, |vec| vec.insert(index, value));
+		index
+	}
+
+	/// Replaces the elements in `range` with `replace_with`, returning the removed elements.
+	/// See [`Vec::splice`], cloning only occurs if this `CowArc` is shared
+	///
+	/// # Panics
+	///
+	/// Panics if `range` is out of bounds
+	pub fn splice<I: IntoIterator<Item = T>>(
+		this: &mut Self,
+		range: Range<usize>,
+		replace_with: I,
+	) -> Vec<T> {
+		Self::with_vec(this, |vec| vec.splice(range, replace_with).collect())
+	}
+
+	/// Slices can't grow in place: moves (or clones if shared) the elements in a [`Vec`],
+	/// lets `f` mutate it, then stores the result in a new allocation
+	fn with_vec<R>(this: &mut Self, f: impl FnOnce(&mut Vec<T>) -> R) -> R {
+		let mut vec = pipeline!(mem::replace(this, Self::from([])) => Self::into_vec);
+		let result = f(&mut vec);
+		*this = Self::from(vec);
+		result
+	}
+}
+
+impl<T> CowArc<[T]> {
+	/// Wraps a slice already shared as an `Arc<[T]>`, without copying it.
+	/// Same as `CowArc::from(arc)` or [`CowArc::from_arc`], without type annotations
+	#[must_use]
+	pub const fn from_arc_slice(arc: Arc<[T]>) -> Self {
+		Self::from_arc(arc)
+	}
+
+	/// Combines two slices element by element into a new one,
+	/// as long as the shortest of the two.
+	/// The result is directly collected in a single allocation of the right size
+	pub fn zip_with<U, V>(
+		a: &Self,
+		b: &CowArc<[U]>,
+		mut f: impl FnMut(&T, &U) -> V,
+	) -> CowArc<[V]> {
+		let zipped: Arc<[V]> = a.iter().zip(b.iter()).map(|(x, y)| f(x, y)).collect();
+		CowArc::from_arc(zipped)
+	}
+
+	/// Returns an empty slice.
+	/// Unlike the `str` version, it can't be cached (a static can't be generic over `T`),
+	/// but only the reference counts are allocated
+	#[must_use]
+	pub fn empty() -> Self {
+		Self::from([])
+	}
+
+	/// Binary searches this sorted slice, see [`slice::binary_search`]
+	///
+	/// # Errors
+	///
+	/// Returns the index where `x` could be inserted to keep the slice sorted if it is not found
+	#[inline]
+	pub fn binary_search(&self, x: &T) -> Result<usize, usize>
+	where
+		T: Ord,
+	{
+		self.deref().binary_search(x)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn into_vec() {
+		let unique: CowArc<[String]> = CowArc::from(["a".to_owned(), "b".to_owned()]);
+		let first_element = unique[0].as_ptr();
+		let vec = CowArc::into_vec(unique);
+		assert_eq!(vec, ["a", "b"]);
+		assert_eq!(vec[0].as_ptr(), first_element); // Moved, not cloned
+
+		let shared: CowArc<[String]> = CowArc::from(["a".to_owned()]);
+		let clone = shared.clone();
+		assert_eq!(CowArc::into_vec(shared), ["a"]);
+		assert_eq!(&*clone, ["a"]);
+	}
+
+	#[test]
+	fn try_into_array() {
+		let cow_arc: CowArc<[i32]> = CowArc::from([1, 2, 3]);
+		let array: [i32; 3] = cow_arc.try_into().unwrap();
+		assert_eq!(array, [1, 2, 3]);
+
+		let cow_arc: CowArc<[i32]> = CowArc::from([1, 2, 3]);
+		let error = <[i32; 2]>::try_from(cow_arc.clone()).unwrap_err();
+		assert_eq!(error, cow_arc);
+	}
+
+	#[test]
+	fn from_slice() {
+		let vec = vec![1, 2];
+		let slice = CowArc::from_slice(&vec);
+		assert_eq!(&*slice, [1, 2]);
+	}
+
+	#[test]
+	fn from_arc_slice() {
+		let shared: Arc<[u8]> = Arc::from(&b"bytes"[..]);
+		let address = shared.as_ptr();
+		assert_eq!(CowArc::from_arc_slice(shared).as_ptr(), address);
+	}
+
+	#[test]
+	fn get_cloned() {
+		let slice: CowArc<[String]> = CowArc::from(["a".to_owned(), "b".to_owned()]);
+		let second = slice.get_cloned(1);
+		drop(slice);
+		assert_eq!(second.as_deref(), Some("b"));
+
+		let slice: CowArc<[i32]> = CowArc::from([1]);
+		assert_eq!(slice.get_cloned(1), None);
+	}
+
+	#[test]
+	fn fill() {
+		let mut buffer: CowArc<[i32]> = CowArc::from([1, 2, 3]);
+		let original = buffer.clone();
+		CowArc::fill(&mut buffer, 0);
+		assert_eq!(&*buffer, [0, 0, 0]);
+		assert_eq!(&*original, [1, 2, 3]);
+
+		let address = buffer.as_ptr();
+		let mut next = 0;
+		CowArc::fill_with(&mut buffer, || {
+			next += 1;
+			next
+		});
+		assert_eq!(&*buffer, [1, 2, 3]);
+		assert_eq!(buffer.as_ptr(), address); // Unique, filled in place
+	}
+
+	#[test]
+	fn splice() {
+		let mut slice: CowArc<[i32]> = CowArc::from([1, 2, 3, 4]);
+		let original = slice.clone();
+
+		assert_eq!(CowArc::splice(&mut slice, 1..3, [7, 8, 9]), [2, 3]);
+		assert_eq!(&*slice, [1, 7, 8, 9, 4]);
+		assert_eq!(&*original, [1, 2, 3, 4]);
+
+		assert_eq!(CowArc::splice(&mut slice, 0..0, [0]), []);
+		assert_eq!(&*slice, [0, 1, 7, 8, 9, 4]);
+	}
+
+	#[test]
+	fn insert_sorted() {
+		let mut sorted: CowArc<[i32]> = CowArc::from([1, 3, 5]);
+		let original = sorted.clone();
+		assert_eq!(sorted.binary_search(&3), Ok(1));
+
+		assert_eq!(CowArc::insert_sorted(&mut sorted, 4), 2);
+		assert_eq!(CowArc::insert_sorted(&mut sorted, 0), 0);
+		assert_eq!(&*sorted, [0, 1, 3, 4, 5]);
+		assert_eq!(&*original, [1, 3, 5]);
+	}
+
+	#[test]
+	fn empty() {
+		let empty = CowArc::<[String]>::empty();
+		assert!(empty.is_empty());
+		assert_eq!(empty, CowArc::<[String]>::empty());
+	}
+
+	#[test]
+	fn zip_with() {
+		let a: CowArc<[i32]> = CowArc::from([1, 2, 3]);
+		let b: CowArc<[i32]> = CowArc::from([10, 20]);
+		let sum = CowArc::zip_with(&a, &b, |x, y| x + y);
+		assert_eq!(&*sum, [11, 22]);
+		assert_eq!(&*a, [1, 2, 3]);
+	}
+
+	#[test]
+	fn rotate() {
+		let mut ring: CowArc<[i32]> = CowArc::from([1, 2, 3, 4]);
+		let original = ring.clone();
+		CowArc::rotate_left(&mut ring, 1);
+		assert_eq!(&*ring, [2, 3, 4, 1]);
+		assert_eq!(&*original, [1, 2, 3, 4]);
+
+		let address = ring.as_ptr();
+		CowArc::rotate_right(&mut ring, 2);
+		assert_eq!(&*ring, [4, 1, 2, 3]);
+		assert_eq!(ring.as_ptr(), address); // Unique, rotated in place
+	}
+}
@@ -0,0 +1,82 @@
+use crate::sync::CowArc;
+use std::sync::{Arc, OnceLock};
+
+impl CowArc<str> {
+	/// Wraps a string already shared as an `Arc<str>` (e.g. by an interner), without copying it.
+	/// Same as `CowArc::from(arc)` or [`CowArc::from_arc`], without type annotations
+	#[must_use]
+	pub const fn from_arc_str(arc: Arc<str>) -> Self {
+		Self::from_arc(arc)
+	}
+
+	/// Returns an empty string, sharing a single allocation across all threads
+	#[must_use]
+	pub fn empty() -> Self {
+		static EMPTY: OnceLock<CowArc<str>> = OnceLock::new();
+		EMPTY.get_or_init(|| Self::from("")).clone()
+	}
+
+	/// Copies a string into a new allocation. Unlike `CowArc::from`, it takes `&String`
+	/// through deref coercion: a `From<&String>` impl would conflict with the generic `From`
+	#[must_use]
+	#[allow(clippy::should_implement_trait)] // Infallible, unlike FromStr
+	pub fn from_str(string: &str) -> Self {
+		Self::from(string)
+	}
+
+	/// Concatenates `parts` into one `String` allocated with the total length,
+	/// then copies it once into the shared allocation,
+	/// instead of reallocating at each part like repeated `+` would
+	pub fn concat_strs(parts: impl IntoIterator<Item = Self>) -> Self {
+		let parts: Vec<Self> = parts.into_iter().collect();
+		let mut string = String::with_capacity(parts.iter().map(|part| part.len()).sum());
+		for part in &parts {
+			string.push_str(part);
+		}
+		Self::from(string)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn empty() {
+		let empty = CowArc::<str>::empty();
+		assert!(empty.is_empty());
+		assert!(CowArc::ptr_eq(&empty, &CowArc::<str>::empty())); // Cached, not reallocated
+	}
+
+	#[test]
+	fn concat_strs() {
+		let comma: CowArc<str> = CowArc::from(", ");
+		let parts: [CowArc<str>; 4] = [
+			CowArc::from("Hello"),
+			comma.clone(),
+			CowArc::from("World"),
+			comma,
+		];
+		assert_eq!(&*CowArc::concat_strs(parts), "Hello, World, ");
+		assert!(CowArc::concat_strs([]).is_empty());
+	}
+
+	#[test]
+	fn from_str() {
+		let string = String::from("Hello");
+		let shared = CowArc::from_str(&string);
+		assert_eq!(&*shared, "Hello");
+	}
+
+	#[test]
+	fn from_arc_str() {
+		let interned: Arc<str> = Arc::from("interned");
+		let address = interned.as_ptr();
+		assert_eq!(
+			CowArc::from_arc_str(Arc::clone(&interned)).as_ptr(),
+			address
+		);
+		let cow_arc: CowArc<str> = CowArc::from(interned);
+		assert_eq!(cow_arc.as_ptr(), address); // The pointer is moved, not the string
+	}
+}
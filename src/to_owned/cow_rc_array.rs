@@ -0,0 +1,138 @@
+use crate::rc::CowRc;
+use std::{
+	borrow::{Borrow, Cow},
+	fmt::Debug,
+	hash::{Hash, Hasher},
+	ops::Deref,
+	ptr,
+};
+use sugaru::pipeline;
+
+/// Comme un [[T; N]] mais [`ToOwned`] donne un [`CowRc<[T; N]>`] et non un [[T; N]]
+#[repr(transparent)]
+#[derive(Eq, PartialEq, Debug, Ord, PartialOrd)]
+pub struct ToCowRcArray<T, const N: usize> {
+	pub array: [T; N],
+}
+
+/// Hashes like an array (itself hashed like a slice)
+impl<T: Hash, const N: usize> Hash for ToCowRcArray<T, N> {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		self.array.hash(state);
+	}
+}
+
+impl<T, const N: usize> ToCowRcArray<T, N> {
+	pub const fn from_array(array: &[T; N]) -> &Self {
+		let ptr = ptr::from_ref(array).cast::<Self>();
+		unsafe { &*ptr }
+	}
+
+	pub fn from_array_mut(array: &mut [T; N]) -> &mut Self {
+		let ptr = pipeline!(array |> ptr::from_mut |> Self::from_array_mut_ptr);
+		unsafe { &mut *ptr }
+	}
+
+	const fn from_array_mut_ptr(array: *mut [T; N]) -> *mut Self {
+		array.cast()
+	}
+}
+
+impl<T: Clone, const N: usize> ToOwned for ToCowRcArray<T, N> {
+	type Owned = CowRc<[T; N]>;
+
+	fn to_owned(&self) -> Self::Owned {
+		CowRc::new(self.array.clone())
+	}
+}
+
+impl<T, const N: usize> Borrow<ToCowRcArray<T, N>> for CowRc<[T; N]> {
+	fn borrow(&self) -> &ToCowRcArray<T, N> {
+		ToCowRcArray::from_array(self)
+	}
+}
+
+impl<T, const N: usize> Deref for ToCowRcArray<T, N> {
+	type Target = [T; N];
+
+	fn deref(&self) -> &Self::Target {
+		&self.array
+	}
+}
+
+impl<T, const N: usize, AsRefArray: ?Sized> AsRef<AsRefArray> for ToCowRcArray<T, N>
+where
+	[T; N]: AsRef<AsRefArray>,
+{
+	fn as_ref(&self) -> &AsRefArray {
+		self.array.as_ref()
+	}
+}
+
+impl<'a, T, const N: usize> From<&'a [T; N]> for &'a ToCowRcArray<T, N> {
+	fn from(value: &'a [T; N]) -> Self {
+		ToCowRcArray::from_array(value)
+	}
+}
+
+impl<'a, T, const N: usize> From<&'a ToCowRcArray<T, N>> for &'a [T; N] {
+	fn from(value: &'a ToCowRcArray<T, N>) -> Self {
+		&value.array
+	}
+}
+
+impl<'a, T: Clone, const N: usize> From<&'a ToCowRcArray<T, N>> for Cow<'a, ToCowRcArray<T, N>> {
+	fn from(value: &'a ToCowRcArray<T, N>) -> Self {
+		Cow::Borrowed(value)
+	}
+}
+
+impl<T: Clone, const N: usize> From<CowRc<[T; N]>> for Cow<'_, ToCowRcArray<T, N>> {
+	fn from(value: CowRc<[T; N]>) -> Self {
+		Cow::Owned(value)
+	}
+}
+
+impl<T: Clone, const N: usize> CowRc<[T; N]> {
+	#[must_use]
+	/// Borrows this array as a [`Cow`],
+	/// avoiding cloning when the array is not mutated.
+	/// Please note cloning is cheap if this Rc is unique.
+	/// Use [`DerefMut`](std::ops::DerefMut) if you're sure you need to mutate
+	pub fn borrow_cow(&self) -> Cow<'_, ToCowRcArray<T, N>> {
+		Cow::Borrowed(ToCowRcArray::from_array(self))
+	}
+}
+
+impl<T, const N: usize, ComparableToArray: ?Sized> PartialEq<ComparableToArray>
+	for ToCowRcArray<T, N>
+where
+	[T; N]: PartialEq<ComparableToArray>,
+{
+	fn eq(&self, other: &ComparableToArray) -> bool {
+		self.array == *other
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn deref_test() {
+		let to_rc_array: &ToCowRcArray<i32, 3> = ToCowRcArray::from_array(&[1, 2, 3]);
+		let rc_array: CowRc<[i32; 3]> = to_rc_array.to_owned();
+		assert_eq!(rc_array.len(), 3);
+		assert_eq!(*rc_array, [1, 2, 3]);
+	}
+
+	#[test]
+	fn cow() {
+		let mut cow: Cow<'_, ToCowRcArray<i32, 3>> =
+			CowRc::new([1, 2, 3]).borrow_cow().into_owned().into();
+		// Cow deref sur ToCowRcArray qui deref sur [T; N]
+		assert_eq!(cow.len(), 3); // Le double deref a bien marché
+		cow.to_mut()[0] = 4;
+		assert_eq!(*cow, [4, 2, 3]);
+	}
+}
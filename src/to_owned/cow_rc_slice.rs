@@ -1,10 +1,14 @@
-use crate::rc::CowRc;
+use crate::rc::{CowRc, WeakCowRc};
 use std::{
 	borrow::{Borrow, Cow},
+	cmp::Ordering,
 	fmt::Debug,
-	ops::Deref,
+	hash::{Hash, Hasher},
+	iter,
+	mem::{self, ManuallyDrop},
+	ops::{Deref, Range},
 	ptr,
-	rc::Rc,
+	rc::{Rc, Weak},
 };
 use sugaru::pipeline;
 
@@ -15,6 +19,14 @@ pub struct ToCowRcSlice<T> {
 	pub slice: [T],
 }
 
+/// Hashes like a slice, so that `[T]`, `ToCowRcSlice<T>` and `CowRc<[T]>` keys are interchangeable
+/// in hash maps through [`Borrow`]
+impl<T: Hash> Hash for ToCowRcSlice<T> {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		self.slice.hash(state);
+	}
+}
+
 impl<T> ToCowRcSlice<T> {
 	pub fn from_array<const N: usize>(array: &[T; N]) -> &Self {
 		Self::from_slice(&array[..])
@@ -37,6 +49,10 @@ impl<T> ToCowRcSlice<T> {
 	const fn from_slice_ptr(slice: *const [T]) -> *const Self {
 		slice as _
 	}
+
+	const fn into_slice_mut_ptr(this: *mut Self) -> *mut [T] {
+		this as _
+	}
 }
 
 impl<T: Clone> ToOwned for ToCowRcSlice<T> {
@@ -53,12 +69,6 @@ impl<T> Borrow<ToCowRcSlice<T>> for CowRc<[T]> {
 	}
 }
 
-impl<T> Borrow<[T]> for CowRc<[T]> {
-	fn borrow(&self) -> &[T] {
-		self
-	}
-}
-
 impl<T> Deref for ToCowRcSlice<T> {
 	type Target = [T];
 
@@ -90,7 +100,22 @@ impl<'a, T> From<&'a ToCowRcSlice<T>> for &'a [T] {
 
 impl<T: Clone> From<CowRc<[T]>> for Vec<T> {
 	fn from(value: CowRc<[T]>) -> Self {
-		value.deref().into()
+		CowRc::into_vec(value)
+	}
+}
+
+impl<T: Clone, const N: usize> TryFrom<CowRc<[T]>> for [T; N] {
+	type Error = CowRc<[T]>;
+
+	/// Moves the elements out of the slice when the `CowRc` is the only strong pointer,
+	/// clones them otherwise.
+	/// Gives back the `CowRc` untouched if its length is not `N`
+	fn try_from(value: CowRc<[T]>) -> Result<Self, Self::Error> {
+		if value.len() == N {
+			pipeline!(value => CowRc::into_vec => Self::try_from).map_err(CowRc::from)
+		} else {
+			Err(value)
+		}
 	}
 }
 
@@ -116,6 +141,22 @@ where
 	}
 }
 
+impl<T> From<Box<ToCowRcSlice<T>>> for CowRc<[T]> {
+	/// The elements are moved (not cloned) in a new allocation,
+	/// since an `Rc` can't reuse a `Box` allocation (it needs room for the reference counts)
+	fn from(value: Box<ToCowRcSlice<T>>) -> Self {
+		unsafe {
+			pipeline!(value
+				|> Box::into_raw
+				|> ToCowRcSlice::into_slice_mut_ptr
+				|> Box::from_raw
+				|> Rc::<[T]>::from
+				|> Self::from_rc
+			)
+		}
+	}
+}
+
 impl<T: Clone> From<&ToCowRcSlice<T>> for Rc<ToCowRcSlice<T>> {
 	fn from(value: &ToCowRcSlice<T>) -> Self {
 		#[allow(unused_braces)]
@@ -151,6 +192,258 @@ impl<T: Clone> CowRc<[T]> {
 	pub fn borrow_cow(&self) -> Cow<'_, ToCowRcSlice<T>> {
 		Cow::Borrowed(ToCowRcSlice::from_slice(self))
 	}
+
+	/// Returns the first element and the rest of the slice as a borrowed [`Cow`],
+	/// or `None` if it is empty
+	#[must_use]
+	pub fn split_first_cow(&self) -> Option<(&T, Cow<'_, ToCowRcSlice<T>>)> {
+		let (first, rest) = self.split_first()?;
+		Some((
+			first,
+			pipeline!(rest |> ToCowRcSlice::from_slice |> Cow::Borrowed),
+		))
+	}
+
+	/// Returns the last element and the rest of the slice as a borrowed [`Cow`],
+	/// or `None` if it is empty
+	#[must_use]
+	pub fn split_last_cow(&self) -> Option<(&T, Cow<'_, ToCowRcSlice<T>>)> {
+		let (last, rest) = self.split_last()?;
+		Some((
+			last,
+			pipeline!(rest |> ToCowRcSlice::from_slice |> Cow::Borrowed),
+		))
+	}
+
+	/// Borrows the elements in `range` as a [`Cow`], without cloning them
+	///
+	/// # Panics
+	///
+	/// Panics if `range` is out of bounds, like slicing
+	#[must_use]
+	pub fn slice_cow(&self, range: Range<usize>) -> Cow<'_, ToCowRcSlice<T>> {
+		pipeline!(&self[range] |> ToCowRcSlice::from_slice |> Cow::Borrowed)
+	}
+
+	/// Borrows the elements in chunks of `size` as [`Cow`]s, without cloning them.
+	/// The last chunk is shorter if `size` does not divide the length, like [`slice::chunks`]
+	///
+	/// # Panics
+	///
+	/// Panics if `size` is 0
+	pub fn chunks_cow(&self, size: usize) -> impl Iterator<Item = Cow<'_, ToCowRcSlice<T>>> {
+		self.chunks(size)
+			.map(|chunk| pipeline!(chunk |> ToCowRcSlice::from_slice |> Cow::Borrowed))
+	}
+
+	/// Wraps this slice in an owned [`Cow`] with a `'static` lifetime (no cloning),
+	/// to return it from functions without borrowing
+	#[must_use]
+	#[allow(clippy::wrong_self_convention)] // Named after borrow_cow, which it complements
+	pub const fn to_owned_cow(self) -> Cow<'static, ToCowRcSlice<T>>
+	where
+		T: 'static,
+	{
+		Cow::Owned(self)
+	}
+
+	/// Returns this slice sorted, as a [`Cow`]:
+	/// borrowed if it is already sorted (no allocation), owned otherwise
+	#[must_use]
+	pub fn sorted_cow(&self) -> Cow<'_, ToCowRcSlice<T>>
+	where
+		T: Ord,
+	{
+		if self.is_sorted() {
+			self.borrow_cow()
+		} else {
+			let mut vec = self.to_vec();
+			vec.sort();
+			pipeline!(vec |> Self::from |> Cow::Owned)
+		}
+	}
+
+	/// Copies a slice into a new allocation. Unlike `CowRc::from`, it takes `&Vec<T>`
+	/// through deref coercion: a `From<&Vec<T>>` impl would conflict with the generic `From`
+	#[must_use]
+	pub fn from_slice(slice: &[T]) -> Self {
+		Self::from(slice)
+	}
+
+	/// Returns a clone of the element at `index`, or `None` if out of bounds,
+	/// without borrowing the shared slice
+	#[must_use]
+	pub fn get_cloned(&self, index: usize) -> Option<T> {
+		self.get(index).cloned()
+	}
+
+	/// Sets every element to `value`: in place if this `CowRc` is unique,
+	/// in a new allocation otherwise (without cloning the previous elements)
+	pub fn fill(this: &mut Self, value: T) {
+		if Self::needs_cloning_to_mutate(this) {
+			*this = iter::repeat_n(value, this.len())
+				.collect::<Rc<[T]>>()
+				.into();
+		} else {
+			Rc::make_mut(Self::as_rc_mut(this)).fill(value);
+		}
+	}
+
+	/// Sets every element to the values returned by `f`, see [`CowRc::fill`]
+	pub fn fill_with(this: &mut Self, f: impl FnMut() -> T) {
+		if Self::needs_cloning_to_mutate(this) {
+			*this = iter::repeat_with(f)
+				.take(this.len())
+				.collect::<Rc<[T]>>()
+				.into();
+		} else {
+			Rc::make_mut(Self::as_rc_mut(this)).fill_with(f);
+		}
+	}
+
+	/// Rotates the elements in place, cloning them first if this `CowRc` is shared.
+	/// See [`slice::rotate_left`]
+	///
+	/// # Panics
+	///
+	/// Panics if `mid` is greater than the length
+	pub fn rotate_left(this: &mut Self, mid: usize) {
+		Rc::make_mut(Self::as_rc_mut(this)).rotate_left(mid);
+	}
+
+	/// Rotates the elements in place, cloning them first if this `CowRc` is shared.
+	/// See [`slice::rotate_right`]
+	///
+	/// # Panics
+	///
+	/// Panics if `k` is greater than the length
+	pub fn rotate_right(this: &mut Self, k: usize) {
+		Rc::make_mut(Self::as_rc_mut(this)).rotate_right(k);
+	}
+
+	/// Converts this slice into a [`Vec`].
+	/// The elements are moved out if this is the only strong pointer
+	/// (any [`WeakCowRc`](crate::rc::WeakCowRc) is disassociated), cloned otherwise
+	#[must_use]
+	pub fn into_vec(this: Self) -> Vec<T> {
+		if Self::needs_cloning_to_mutate(&this) {
+			return this.to_vec();
+		}
+		let rc = Self::unwrap_rc(this);
+		let len = rc.len();
+		let mut vec = Vec::with_capacity(len);
+		unsafe {
+			ptr::copy_nonoverlapping(rc.as_ptr(), vec.as_mut_ptr(), len);
+			vec.set_len(len);
+			// The elements now belong to vec, the allocation must be freed without dropping them
+			drop(Rc::from_raw(Rc::into_raw(rc) as *const [ManuallyDrop<T>]));
+		}
+		vec
+	}
+
+	/// Converts this slice into a [`Box<[T]>`](Box).
+	/// An `Rc` allocation stores its counts before the elements, so it can't become a `Box`:
+	/// the elements are moved (see [`CowRc::into_vec`]) into a new allocation of the exact size
+	/// if this `CowRc` is unique, cloned otherwise
+	#[must_use]
+	pub fn into_boxed_slice(this: Self) -> Box<[T]> {
+		pipeline!(this => Self::into_vec => Vec::into_boxed_slice)
+	}
+
+	/// Inserts `value` at its sorted position, returning its index.
+	/// The slice must already be sorted.
+	/// Cloning only occurs if this `CowRc` is shared
+	pub fn insert_sorted(this: &mut Self, value: T) -> usize
+	where
+		T: Ord,
+	{
+		let (Ok(index) | Err(index)) = this.binary_search(&value);
+		Self::with_vec(this, |vec| vec.insert(index, value));
+		index
+	}
+
+	/// Replaces the elements in `range` with `replace_with`, returning the removed elements.
+	/// See [`Vec::splice`], cloning only occurs if this `CowRc` is shared
+	///
+	/// # Panics
+	///
+	/// Panics if `range` is out of bounds
+	pub fn splice<I: IntoIterator<Item = T>>(
+		this: &mut Self,
+		range: Range<usize>,
+		replace_with: I,
+	) -> Vec<T> {
+		Self::with_vec(this, |vec| vec.splice(range, replace_with).collect())
+	}
+
+	/// Slices can't grow in place: moves (or clones if shared) the elements in a [`Vec`],
+	/// lets `f` mutate it, then stores the result in a new allocation
+	fn with_vec<R>(this: &mut Self, f: impl FnOnce(&mut Vec<T>) -> R) -> R {
+		let mut vec = pipeline!(mem::replace(this, Self::from([])) => Self::into_vec);
+		let result = f(&mut vec);
+		*this = Self::from(vec);
+		result
+	}
+}
+
+impl<T> WeakCowRc<[T]> {
+	/// Returns a weak pointer which never upgrades, like [`WeakCowRc::new`] for sized types
+	/// (e.g. for a field whose value doesn't exist yet). Doesn't allocate
+	#[must_use]
+	pub const fn dangling() -> Self {
+		let empty: Weak<[T]> = Weak::<[T; 0]>::new();
+		Self::from_weak(empty)
+	}
+}
+
+impl<T> CowRc<[T]> {
+	/// Combines two slices element by element into a new one,
+	/// as long as the shortest of the two.
+	/// The result is directly collected in a single allocation of the right size
+	pub fn zip_with<U, V>(a: &Self, b: &CowRc<[U]>, mut f: impl FnMut(&T, &U) -> V) -> CowRc<[V]> {
+		let zipped: Rc<[V]> = a.iter().zip(b.iter()).map(|(x, y)| f(x, y)).collect();
+		CowRc::from_rc(zipped)
+	}
+
+	/// Returns an empty slice.
+	/// Unlike the `str` version, it can't be cached (a static can't be generic over `T`),
+	/// but only the reference counts are allocated
+	#[must_use]
+	pub fn empty() -> Self {
+		Self::from([])
+	}
+
+	/// Binary searches this sorted slice, see [`slice::binary_search`]
+	///
+	/// # Errors
+	///
+	/// Returns the index where `x` could be inserted to keep the slice sorted if it is not found
+	#[inline]
+	pub fn binary_search(&self, x: &T) -> Result<usize, usize>
+	where
+		T: Ord,
+	{
+		self.deref().binary_search(x)
+	}
+
+	/// Reinterprets the elements of this slice as `U`, without copying nor reallocating
+	/// (the other pointers to the same allocation still see `T` elements).
+	/// Useful for `#[repr(transparent)]` newtypes over the elements.
+	///
+	/// # Safety
+	///
+	/// `T` and `U` must have the same size and alignment,
+	/// and every `T` of the slice must be a valid `U`
+	#[must_use]
+	pub unsafe fn cast<U>(this: Self) -> CowRc<[U]> {
+		debug_assert_eq!(mem::size_of::<T>(), mem::size_of::<U>());
+		debug_assert_eq!(mem::align_of::<T>(), mem::align_of::<U>());
+		unsafe {
+			CowRc::from_rc(Rc::from_raw(
+				Rc::into_raw(Self::unwrap_rc(this)) as *const [U]
+			))
+		}
+	}
 }
 
 impl<T, ComparableToSlice: ?Sized> PartialEq<ComparableToSlice> for ToCowRcSlice<T>
@@ -183,6 +476,41 @@ where
 	}
 }
 
+impl<T: Clone> FromIterator<CowRc<[T]>> for Vec<T> {
+	/// Flattens shared slices, elements of unique slices are moved rather than cloned
+	fn from_iter<Iterator: IntoIterator<Item = CowRc<[T]>>>(iter: Iterator) -> Self {
+		let mut vec = Self::new();
+		for piece in iter {
+			vec.extend(CowRc::into_vec(piece));
+		}
+		vec
+	}
+}
+
+impl<T: PartialEq> PartialEq<[T]> for CowRc<[T]> {
+	fn eq(&self, other: &[T]) -> bool {
+		**self == *other
+	}
+}
+
+impl<T: PartialEq> PartialEq<&[T]> for CowRc<[T]> {
+	fn eq(&self, other: &&[T]) -> bool {
+		**self == **other
+	}
+}
+
+impl<T: PartialOrd> PartialOrd<[T]> for CowRc<[T]> {
+	fn partial_cmp(&self, other: &[T]) -> Option<Ordering> {
+		(**self).partial_cmp(other)
+	}
+}
+
+impl<T: PartialOrd> PartialOrd<&[T]> for CowRc<[T]> {
+	fn partial_cmp(&self, other: &&[T]) -> Option<Ordering> {
+		(**self).partial_cmp(*other)
+	}
+}
+
 #[cfg(test)]
 mod test {
 	use super::*;
@@ -201,4 +529,268 @@ mod test {
 		// Cow deref sur ToCowRcStr qui deref sur [T]
 		assert_eq!(cow.len(), 3); // Le double deref a bien marché
 	}
+
+	#[test]
+	fn into_vec() {
+		let unique: CowRc<[String]> = CowRc::from(["a".to_owned(), "b".to_owned()]);
+		let first_element = unique[0].as_ptr();
+		let vec = CowRc::into_vec(unique);
+		assert_eq!(vec, ["a", "b"]);
+		assert_eq!(vec[0].as_ptr(), first_element); // Moved, not cloned
+
+		let shared: CowRc<[String]> = CowRc::from(["a".to_owned()]);
+		let clone = shared.clone();
+		assert_eq!(CowRc::into_vec(shared), ["a"]);
+		assert_eq!(&*clone, ["a"]);
+	}
+
+	#[test]
+	fn into_boxed_slice() {
+		let unique: CowRc<[String]> = CowRc::from(["a".to_owned(), "b".to_owned()]);
+		let first_element = unique[0].as_ptr();
+		let boxed = CowRc::into_boxed_slice(unique);
+		assert_eq!(&*boxed, ["a", "b"]);
+		assert_eq!(boxed[0].as_ptr(), first_element); // Moved, not cloned
+
+		let shared: CowRc<[String]> = CowRc::from(["a".to_owned()]);
+		let clone = shared.clone();
+		let boxed = CowRc::into_boxed_slice(shared);
+		assert_ne!(boxed[0].as_ptr(), clone[0].as_ptr()); // Cloned
+		assert_eq!(&*clone, ["a"]);
+	}
+
+	#[test]
+	fn try_into_array() {
+		let cow_rc: CowRc<[i32]> = CowRc::from([1, 2, 3]);
+		let array: [i32; 3] = cow_rc.try_into().unwrap();
+		assert_eq!(array, [1, 2, 3]);
+
+		let cow_rc: CowRc<[i32]> = CowRc::from([1, 2, 3]);
+		let error = <[i32; 2]>::try_from(cow_rc.clone()).unwrap_err();
+		assert_eq!(error, cow_rc);
+	}
+
+	#[test]
+	fn from_slice() {
+		let vec = vec![1, 2];
+		let slice = CowRc::from_slice(&vec);
+		assert_eq!(&*slice, [1, 2]);
+	}
+
+	#[test]
+	fn dangling_weak() {
+		struct Node {
+			children: WeakCowRc<[Self]>,
+		}
+
+		let node = Node {
+			children: WeakCowRc::<[Node]>::dangling(),
+		};
+		assert!(node.children.upgrade().is_none());
+	}
+
+	#[test]
+	fn get_cloned() {
+		let slice: CowRc<[String]> = CowRc::from(["a".to_owned(), "b".to_owned()]);
+		let second = slice.get_cloned(1);
+		drop(slice);
+		assert_eq!(second.as_deref(), Some("b"));
+
+		let slice: CowRc<[i32]> = CowRc::from([1]);
+		assert_eq!(slice.get_cloned(1), None);
+	}
+
+	#[test]
+	fn fill() {
+		let mut buffer: CowRc<[i32]> = CowRc::from([1, 2, 3]);
+		let original = buffer.clone();
+		CowRc::fill(&mut buffer, 0);
+		assert_eq!(&*buffer, [0, 0, 0]);
+		assert_eq!(&*original, [1, 2, 3]);
+
+		let address = buffer.as_ptr();
+		let mut next = 0;
+		CowRc::fill_with(&mut buffer, || {
+			next += 1;
+			next
+		});
+		assert_eq!(&*buffer, [1, 2, 3]);
+		assert_eq!(buffer.as_ptr(), address); // Unique, filled in place
+	}
+
+	#[test]
+	fn splice() {
+		let mut slice: CowRc<[i32]> = CowRc::from([1, 2, 3, 4]);
+		let original = slice.clone();
+
+		assert_eq!(CowRc::splice(&mut slice, 1..3, [7, 8, 9]), [2, 3]);
+		assert_eq!(&*slice, [1, 7, 8, 9, 4]);
+		assert_eq!(&*original, [1, 2, 3, 4]);
+
+		assert_eq!(CowRc::splice(&mut slice, 0..0, [0]), []);
+		assert_eq!(&*slice, [0, 1, 7, 8, 9, 4]);
+	}
+
+	#[test]
+	fn insert_sorted() {
+		let mut sorted: CowRc<[i32]> = CowRc::from([1, 3, 5]);
+		let original = sorted.clone();
+		assert_eq!(sorted.binary_search(&3), Ok(1));
+
+		assert_eq!(CowRc::insert_sorted(&mut sorted, 4), 2);
+		assert_eq!(CowRc::insert_sorted(&mut sorted, 0), 0);
+		assert_eq!(&*sorted, [0, 1, 3, 4, 5]);
+		assert_eq!(&*original, [1, 3, 5]);
+	}
+
+	#[test]
+	fn flatten() {
+		let shared: CowRc<[i32]> = CowRc::from([1, 2]);
+		let vec: Vec<i32> = [shared.clone(), CowRc::from([3]), shared]
+			.into_iter()
+			.collect();
+		assert_eq!(vec, [1, 2, 3, 1, 2]);
+	}
+
+	#[test]
+	fn compare_with_slices() {
+		let cow_rc: CowRc<[i32]> = CowRc::from([1, 2, 3]);
+		let (greater, lower, equal): (&[i32], &[i32], &[i32]) = (&[1, 3], &[1, 2], &[1, 2, 3]);
+		assert!(cow_rc < greater);
+		assert!(cow_rc > lower);
+		assert_eq!(cow_rc, equal);
+		assert!(cow_rc >= *equal);
+	}
+
+	#[test]
+	fn from_boxed() {
+		let boxed: Box<ToCowRcSlice<String>> = Box::from(vec!["a".to_owned()]);
+		let element = boxed[0].as_ptr();
+		let cow_rc: CowRc<[String]> = CowRc::from(boxed);
+		assert_eq!(&*cow_rc, ["a"]);
+		assert_eq!(cow_rc[0].as_ptr(), element); // The elements are moved, not cloned
+	}
+
+	#[test]
+	fn sorted_cow() {
+		let sorted: CowRc<[i32]> = CowRc::from([1, 2, 3]);
+		assert!(matches!(sorted.sorted_cow(), Cow::Borrowed(slice) if *slice == [1, 2, 3]));
+
+		let unsorted: CowRc<[i32]> = CowRc::from([3, 1, 2]);
+		assert!(matches!(unsorted.sorted_cow(), Cow::Owned(slice) if *slice == [1, 2, 3]));
+		assert_eq!(&*unsorted, [3, 1, 2]);
+	}
+
+	#[test]
+	fn cast() {
+		#[repr(transparent)]
+		#[derive(Debug, Clone, Copy, PartialEq)]
+		struct Celsius(f32);
+
+		let raw: CowRc<[f32]> = CowRc::from([12.5, -3.0]);
+		let shared = raw.clone();
+		let ptr = raw.as_ptr();
+		let celsius: CowRc<[Celsius]> = unsafe { CowRc::cast(raw) };
+		assert_eq!(&*celsius, [Celsius(12.5), Celsius(-3.0)]);
+		assert_eq!(celsius.as_ptr().cast::<f32>(), ptr); // Same allocation
+		assert_eq!(&*shared, [12.5, -3.0]);
+	}
+
+	#[test]
+	fn to_owned_cow() {
+		fn doubled(values: &[i32]) -> Cow<'static, ToCowRcSlice<i32>> {
+			values
+				.iter()
+				.map(|value| value * 2)
+				.collect::<CowRc<[i32]>>()
+				.to_owned_cow()
+		}
+
+		let cow = doubled(&[1, 2]);
+		assert!(matches!(cow, Cow::Owned(_)));
+		assert_eq!(&**cow, [2, 4]);
+	}
+
+	#[test]
+	fn empty() {
+		let empty = CowRc::<[String]>::empty();
+		assert!(empty.is_empty());
+		assert_eq!(empty, CowRc::<[String]>::empty());
+	}
+
+	#[test]
+	fn zip_with() {
+		let a: CowRc<[i32]> = CowRc::from([1, 2, 3]);
+		let b: CowRc<[i32]> = CowRc::from([10, 20]);
+		let sum = CowRc::zip_with(&a, &b, |x, y| x + y);
+		assert_eq!(&*sum, [11, 22]);
+		assert_eq!(&*a, [1, 2, 3]);
+	}
+
+	#[test]
+	fn split_cow() {
+		let slice: CowRc<[i32]> = CowRc::from([1, 2, 3]);
+		let (first, rest) = slice.split_first_cow().unwrap();
+		assert_eq!((*first, &**rest), (1, &[2, 3][..]));
+		assert!(matches!(rest, Cow::Borrowed(_)));
+		let (last, rest) = slice.split_last_cow().unwrap();
+		assert_eq!((*last, &**rest), (3, &[1, 2][..]));
+
+		let empty = CowRc::<[i32]>::empty();
+		assert!(empty.split_first_cow().is_none());
+		assert!(empty.split_last_cow().is_none());
+	}
+
+	#[test]
+	fn slice_cow() {
+		let buffer: CowRc<[u8]> = CowRc::from(&b"header:body"[..]);
+		let view = buffer.slice_cow(7..11);
+		assert!(matches!(view, Cow::Borrowed(_)));
+		assert_eq!(&view.slice, b"body");
+		assert_eq!(view.slice.as_ptr(), buffer[7..].as_ptr()); // A view into the shared allocation
+	}
+
+	#[test]
+	#[should_panic = "out of range"]
+	fn slice_cow_out_of_range() {
+		let buffer: CowRc<[u8]> = CowRc::from([1, 2]);
+		let _ = buffer.slice_cow(1..3);
+	}
+
+	#[test]
+	fn chunks_cow() {
+		let buffer: CowRc<[i32]> = CowRc::from([1, 2, 3, 4, 5]);
+		let chunks: Vec<_> = buffer.chunks_cow(2).collect();
+		assert_eq!(chunks.len(), 3);
+		assert!(chunks.iter().all(|chunk| matches!(chunk, Cow::Borrowed(_))));
+		assert_eq!(chunks[0].slice.as_ptr(), buffer.as_ptr()); // Views into the shared allocation
+		assert_eq!(&chunks[2].slice, [5]);
+
+		let rebuilt: Vec<i32> = chunks
+			.iter()
+			.flat_map(|chunk| chunk.slice.iter().copied())
+			.collect();
+		assert_eq!(rebuilt, *buffer);
+	}
+
+	#[test]
+	#[should_panic = "chunk size must be non-zero"]
+	fn chunks_cow_zero() {
+		let buffer: CowRc<[i32]> = CowRc::from([1, 2]);
+		let _ = buffer.chunks_cow(0);
+	}
+
+	#[test]
+	fn rotate() {
+		let mut ring: CowRc<[i32]> = CowRc::from([1, 2, 3, 4]);
+		let original = ring.clone();
+		CowRc::rotate_left(&mut ring, 1);
+		assert_eq!(&*ring, [2, 3, 4, 1]);
+		assert_eq!(&*original, [1, 2, 3, 4]);
+
+		let address = ring.as_ptr();
+		CowRc::rotate_right(&mut ring, 2);
+		assert_eq!(&*ring, [4, 1, 2, 3]);
+		assert_eq!(ring.as_ptr(), address); // Unique, rotated in place
+	}
 }
@@ -1,11 +1,15 @@
-use crate::rc::CowRc;
+use crate::rc::{CowRc, WeakCowRc};
 use std::{
 	borrow::{Borrow, Cow},
+	cmp::Ordering,
 	ffi::OsStr,
 	fmt::{Debug, Display, Formatter},
+	hash::{Hash, Hasher},
+	iter::FusedIterator,
 	ops::Deref,
 	ptr,
-	rc::Rc,
+	rc::{Rc, Weak},
+	str::{self, FromStr, Utf8Error},
 };
 use sugaru::pipeline;
 
@@ -16,7 +20,16 @@ pub struct ToCowRcStr {
 	pub str: str,
 }
 
+/// Hashes like a [`str`], so that `str`, `ToCowRcStr` and `CowRc<str>` keys are interchangeable
+/// in hash maps through [`Borrow`]
+impl Hash for ToCowRcStr {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		self.str.hash(state);
+	}
+}
+
 impl ToCowRcStr {
+	#[must_use]
 	pub const fn from_str(string_slice: &str) -> &Self {
 		let ptr = ptr::from_ref(string_slice) as *const Self;
 		unsafe { &*ptr }
@@ -34,6 +47,16 @@ impl ToCowRcStr {
 	const fn from_str_mut_ptr(string_slice: *mut str) -> *mut Self {
 		string_slice as _
 	}
+
+	const fn into_str_mut_ptr(this: *mut Self) -> *mut str {
+		this as _
+	}
+
+	/// Also available on `Box<ToCowRcStr>` through auto-deref
+	#[must_use]
+	pub const fn as_str(&self) -> &str {
+		&self.str
+	}
 }
 
 impl ToOwned for ToCowRcStr {
@@ -50,12 +73,6 @@ impl Borrow<ToCowRcStr> for CowRc<str> {
 	}
 }
 
-impl Borrow<str> for CowRc<str> {
-	fn borrow(&self) -> &str {
-		self
-	}
-}
-
 impl Deref for ToCowRcStr {
 	type Target = str;
 
@@ -119,6 +136,22 @@ where
 	}
 }
 
+impl From<Box<ToCowRcStr>> for CowRc<str> {
+	/// The bytes are moved once in a new allocation,
+	/// since an `Rc` can't reuse a `Box` allocation (it needs room for the reference counts)
+	fn from(value: Box<ToCowRcStr>) -> Self {
+		unsafe {
+			pipeline!(value
+				|> Box::into_raw
+				|> ToCowRcStr::into_str_mut_ptr
+				|> Box::from_raw
+				|> Rc::<str>::from
+				|> Self::from_rc
+			)
+		}
+	}
+}
+
 impl From<&ToCowRcStr> for Rc<ToCowRcStr> {
 	fn from(value: &ToCowRcStr) -> Self {
 		#[allow(unused_braces)]
@@ -154,6 +187,130 @@ impl CowRc<str> {
 	pub fn borrow_cow(&self) -> Cow<'_, ToCowRcStr> {
 		Cow::Borrowed(ToCowRcStr::from_str(self))
 	}
+
+	/// Converts shared bytes into a shared string, reusing the allocation
+	/// (`Rc<[u8]>` and `Rc<str>` have the same layout), whether the bytes are shared or not.
+	///
+	/// # Errors
+	///
+	/// Gives back the bytes along with the [`Utf8Error`] if they aren't valid UTF-8
+	pub fn from_utf8(bytes: CowRc<[u8]>) -> Result<Self, (Utf8Error, CowRc<[u8]>)> {
+		match str::from_utf8(&bytes) {
+			Ok(_) => Ok(unsafe {
+				pipeline!(bytes
+					|> CowRc::unwrap_rc
+					|> Rc::into_raw
+					|> Self::bytes_to_str_ptr
+					|> Rc::from_raw
+					|> Self::from_rc
+				)
+			}),
+			Err(error) => Err((error, bytes)),
+		}
+	}
+
+	/// Converts this string into a [`Box<str>`](Box).
+	/// An `Rc` allocation stores its counts before the bytes, so it can't become a `Box`:
+	/// the bytes are always copied once, whether this `CowRc` is unique or shared
+	#[must_use]
+	#[allow(clippy::needless_pass_by_value)] // Consumes the string, like into_boxed_slice
+	pub fn into_boxed_str(this: Self) -> Box<str> {
+		Box::from(&*this)
+	}
+
+	/// Converts this string into its bytes, reusing the allocation.
+	/// Never copies, even if the string is shared: the other `CowRc`s keep viewing it as a `str`
+	#[must_use]
+	#[doc(alias = "into_bytes_shared")]
+	pub fn into_bytes(this: Self) -> CowRc<[u8]> {
+		unsafe {
+			pipeline!(this
+				|> Self::unwrap_rc
+				|> Rc::into_raw
+				|> Self::str_to_bytes_ptr
+				|> Rc::from_raw
+				|> CowRc::from_rc
+			)
+		}
+	}
+
+	const fn bytes_to_str_ptr(bytes: *const [u8]) -> *const str {
+		bytes as _
+	}
+
+	const fn str_to_bytes_ptr(string: *const str) -> *const [u8] {
+		string as _
+	}
+
+	/// Returns an empty string, sharing a single allocation per thread
+	#[must_use]
+	pub fn empty() -> Self {
+		thread_local! {
+			static EMPTY: CowRc<str> = CowRc::from("");
+		}
+		EMPTY.with(Self::clone)
+	}
+
+	/// Wraps this string in an owned [`Cow`] with a `'static` lifetime (no cloning),
+	/// to return it from functions without borrowing
+	#[must_use]
+	#[allow(clippy::wrong_self_convention)] // Named after borrow_cow, which it complements
+	pub const fn to_owned_cow(self) -> Cow<'static, ToCowRcStr> {
+		Cow::Owned(self)
+	}
+
+	/// Copies a string into a new allocation. Unlike `CowRc::from`, it takes `&String`
+	/// through deref coercion: a `From<&String>` impl would conflict with the generic `From`
+	#[must_use]
+	#[allow(clippy::should_implement_trait)] // Infallible, unlike FromStr
+	pub fn from_str(string: &str) -> Self {
+		Self::from(string)
+	}
+
+	/// Collects `chars`, preallocating one byte per char
+	/// (the exact size for ASCII, a lower bound otherwise)
+	pub fn from_chars<Chars>(chars: Chars) -> Self
+	where
+		Chars: IntoIterator<Item = char>,
+		Chars::IntoIter: ExactSizeIterator,
+	{
+		let chars = chars.into_iter();
+		let mut string = String::with_capacity(chars.len());
+		string.extend(chars);
+		Self::from(string)
+	}
+
+	/// Concatenates `parts` into one `String` allocated with the total length,
+	/// then copies it once into the shared allocation,
+	/// instead of reallocating at each part like repeated `+` would
+	pub fn concat_strs(parts: impl IntoIterator<Item = Self>) -> Self {
+		let parts: Vec<Self> = parts.into_iter().collect();
+		let mut string = String::with_capacity(parts.iter().map(|part| part.len()).sum());
+		for part in &parts {
+			string.push_str(part);
+		}
+		Self::from(string)
+	}
+
+	/// Parses this string into another type, see [`str::parse`]
+	///
+	/// # Errors
+	///
+	/// Returns the [`FromStr::Err`] of `F` if the string can't be parsed
+	pub fn parse<F: FromStr>(&self) -> Result<F, F::Err> {
+		self.deref().parse()
+	}
+}
+
+impl WeakCowRc<str> {
+	/// Returns a weak pointer which never upgrades, like [`WeakCowRc::new`] for sized types
+	/// (e.g. for a field whose value doesn't exist yet). Doesn't allocate
+	#[must_use]
+	pub fn dangling() -> Self {
+		let bytes: Weak<[u8]> = Weak::<[u8; 0]>::new();
+		// A dangling weak never reads its value, and `[u8]` and `str` share their layout
+		unsafe { pipeline!(Weak::from_raw(Weak::into_raw(bytes) as *const str) => Self::from_weak) }
+	}
 }
 
 impl<'a> TryFrom<&'a OsStr> for &'a ToCowRcStr {
@@ -173,6 +330,25 @@ where
 	}
 }
 
+// Box is #[fundamental], so these impls are allowed despite Box being a foreign type
+impl PartialEq<str> for Box<ToCowRcStr> {
+	fn eq(&self, other: &str) -> bool {
+		self.str == *other
+	}
+}
+
+impl PartialEq<&str> for Box<ToCowRcStr> {
+	fn eq(&self, other: &&str) -> bool {
+		self.str == **other
+	}
+}
+
+impl PartialEq<Box<ToCowRcStr>> for &str {
+	fn eq(&self, other: &Box<ToCowRcStr>) -> bool {
+		**self == other.str
+	}
+}
+
 impl Display for ToCowRcStr {
 	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
 		Display::fmt(&self.str, f)
@@ -200,6 +376,88 @@ where
 	}
 }
 
+impl FromIterator<CowRc<str>> for String {
+	/// Concatenates shared strings
+	fn from_iter<Iterator: IntoIterator<Item = CowRc<str>>>(iter: Iterator) -> Self {
+		let mut string = Self::new();
+		for piece in iter {
+			string.push_str(&piece);
+		}
+		string
+	}
+}
+
+impl PartialEq<str> for CowRc<str> {
+	fn eq(&self, other: &str) -> bool {
+		**self == *other
+	}
+}
+
+impl PartialEq<&str> for CowRc<str> {
+	fn eq(&self, other: &&str) -> bool {
+		**self == **other
+	}
+}
+
+impl PartialOrd<str> for CowRc<str> {
+	fn partial_cmp(&self, other: &str) -> Option<Ordering> {
+		(**self).partial_cmp(other)
+	}
+}
+
+impl PartialOrd<&str> for CowRc<str> {
+	/// Allows comparing with literals: `cow_rc < "m"`
+	fn partial_cmp(&self, other: &&str) -> Option<Ordering> {
+		(**self).partial_cmp(*other)
+	}
+}
+
+/// Owning iterator over the [`char`]s of a `CowRc<str>`.
+/// Holds a strong pointer to the string rather than copying it, shared or not
+#[derive(Debug, Clone)]
+pub struct IntoChars {
+	string: CowRc<str>,
+	front: usize,
+	back: usize,
+}
+
+impl Iterator for IntoChars {
+	type Item = char;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let char = self.string[self.front..self.back].chars().next()?;
+		self.front += char.len_utf8();
+		Some(char)
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.string[self.front..self.back].chars().size_hint()
+	}
+}
+
+impl DoubleEndedIterator for IntoChars {
+	fn next_back(&mut self) -> Option<Self::Item> {
+		let char = self.string[self.front..self.back].chars().next_back()?;
+		self.back -= char.len_utf8();
+		Some(char)
+	}
+}
+
+impl FusedIterator for IntoChars {}
+
+impl IntoIterator for CowRc<str> {
+	type Item = char;
+	type IntoIter = IntoChars;
+
+	fn into_iter(self) -> Self::IntoIter {
+		IntoChars {
+			front: 0,
+			back: self.len(),
+			string: self,
+		}
+	}
+}
+
 #[cfg(test)]
 mod test {
 	use super::*;
@@ -217,4 +475,173 @@ mod test {
 		// Cow deref sur ToCowRcStr qui deref sur str
 		assert_eq!(cow.len(), 4); // Le double deref a bien marché
 	}
+
+	#[test]
+	fn concatenate() {
+		let hello: CowRc<str> = CowRc::from("Hello");
+		let string: String = [hello.clone(), CowRc::from(", "), hello]
+			.into_iter()
+			.collect();
+		assert_eq!(string, "Hello, Hello");
+	}
+
+	#[test]
+	fn compare_with_literals() {
+		let cow_rc: CowRc<str> = CowRc::from("hello");
+		assert!(cow_rc < "m");
+		assert!(cow_rc > "a");
+		assert_eq!(cow_rc, "hello");
+		assert!(cow_rc <= *"hello");
+
+		let mut sorted: Vec<CowRc<str>> = vec![CowRc::from("b"), CowRc::from("z")];
+		sorted.sort();
+		let index = sorted.partition_point(|key| key < "m");
+		assert_eq!(index, 1);
+	}
+
+	#[test]
+	fn from_boxed() {
+		let boxed: Box<ToCowRcStr> = Box::from("Hello");
+		let cow_rc: CowRc<str> = CowRc::from(boxed);
+		assert_eq!(&*cow_rc, "Hello");
+		assert!(CowRc::is_unique(&cow_rc));
+	}
+
+	#[test]
+	fn into_chars() {
+		let cow_rc: CowRc<str> = CowRc::from("héllo");
+		let shared = cow_rc.clone();
+		let mut chars = cow_rc.into_iter();
+		assert_eq!(chars.next(), Some('h'));
+		assert_eq!(chars.next_back(), Some('o'));
+		assert_eq!(chars.collect::<String>(), "éll");
+		assert_eq!(&*shared, "héllo");
+
+		let unique: CowRc<str> = CowRc::from("abc");
+		let ptr = unique.as_ptr();
+		let chars = unique.into_iter();
+		assert_eq!(chars.string.as_ptr(), ptr); // No copy
+		assert_eq!(chars.rev().collect::<String>(), "cba");
+	}
+
+	#[test]
+	fn split_and_find() {
+		let csv: CowRc<str> = CowRc::from("a,b,c");
+		let fields: Vec<&str> = csv.split(',').collect();
+		assert_eq!(fields, ["a", "b", "c"]);
+		assert_eq!(csv.find(','), Some(1));
+		assert_eq!(csv.find(';'), None);
+		assert_eq!(csv.split(",b,").collect::<Vec<_>>(), ["a", "c"]); // Any pattern, through Deref
+		assert_eq!(csv.find("b,"), Some(2));
+		assert_eq!(csv.split(char::is_alphabetic).count(), 4);
+	}
+
+	#[test]
+	fn collect() {
+		let from_chars: CowRc<str> = "abc".chars().collect();
+		let from_str_slices: CowRc<str> = ["a", "b", "c"].into_iter().collect();
+		let preallocated = CowRc::from_chars(['a', 'b', 'c']);
+		assert_eq!(from_chars, from_str_slices);
+		assert_eq!(from_chars, preallocated);
+		assert_eq!(CowRc::from_chars(['é']), "é");
+	}
+
+	#[test]
+	fn to_owned_cow() {
+		fn greeting(name: &str) -> Cow<'static, ToCowRcStr> {
+			pipeline!(["Hello ", name] |> String::from_iter |> CowRc::<str>::from).to_owned_cow()
+		}
+
+		let cow = greeting("you");
+		assert!(matches!(cow, Cow::Owned(_)));
+		assert_eq!(&**cow, "Hello you");
+	}
+
+	#[test]
+	fn empty() {
+		let empty = CowRc::<str>::empty();
+		assert!(empty.is_empty());
+		assert!(CowRc::ptr_eq(&empty, &CowRc::<str>::empty())); // Cached, not reallocated
+	}
+
+	#[test]
+	fn compare_boxed() {
+		let boxed: Box<ToCowRcStr> = Box::from("Hello");
+		assert_eq!(boxed, "Hello");
+		assert_eq!("Hello", boxed);
+		assert_eq!(boxed, *"Hello");
+		assert_ne!(boxed, "World");
+		assert_eq!(boxed.as_str(), "Hello");
+	}
+
+	#[test]
+	fn utf8() {
+		let bytes: CowRc<[u8]> = CowRc::from("héllo".as_bytes());
+		let ptr = bytes.as_ptr();
+		let string = CowRc::from_utf8(bytes).unwrap();
+		assert_eq!(string, "héllo");
+		assert_eq!(string.as_ptr(), ptr); // Same allocation
+
+		let bytes = CowRc::into_bytes(string);
+		assert_eq!(&*bytes, "héllo".as_bytes());
+		assert_eq!(bytes.as_ptr(), ptr);
+
+		let shared: CowRc<str> = CowRc::from("shared");
+		let string = shared.clone();
+		let bytes = CowRc::into_bytes(shared);
+		assert_eq!(&*bytes, b"shared");
+		assert_eq!(bytes.as_ptr(), string.as_ptr()); // Still one allocation, seen as both types
+		assert_eq!(&*string, "shared");
+
+		let invalid: CowRc<[u8]> = CowRc::from([0xff, 0xfe]);
+		let (error, bytes) = CowRc::from_utf8(invalid).unwrap_err();
+		assert_eq!(error.valid_up_to(), 0);
+		assert_eq!(&*bytes, [0xff, 0xfe]);
+	}
+
+	#[test]
+	fn parse() {
+		let integer: CowRc<str> = CowRc::from("42");
+		assert_eq!(integer.parse::<i32>(), Ok(42));
+		let float: CowRc<str> = CowRc::from("1.5");
+		assert_eq!(float.parse(), Ok(1.5));
+		assert!(float.parse::<u8>().is_err());
+	}
+
+	#[test]
+	fn concat_strs() {
+		let comma: CowRc<str> = CowRc::from(", ");
+		let parts: [CowRc<str>; 4] = [
+			CowRc::from("Hello"),
+			comma.clone(),
+			CowRc::from("World"),
+			comma,
+		];
+		assert_eq!(&*CowRc::concat_strs(parts), "Hello, World, ");
+		assert!(CowRc::concat_strs([]).is_empty());
+	}
+
+	#[test]
+	fn into_boxed_str() {
+		let string: CowRc<str> = CowRc::from("Hello");
+		let clone = string.clone();
+		let boxed = CowRc::into_boxed_str(string);
+		assert_eq!(&*boxed, "Hello");
+		assert_eq!(&*clone, "Hello");
+	}
+
+	#[test]
+	fn from_str() {
+		let string = String::from("Hello");
+		let shared = CowRc::from_str(&string);
+		assert_eq!(&*shared, "Hello");
+	}
+
+	#[test]
+	fn dangling_weak() {
+		let weak = WeakCowRc::<str>::dangling();
+		let clone = weak.clone();
+		assert!(weak.upgrade().is_none());
+		assert!(clone.upgrade().is_none());
+	}
 }
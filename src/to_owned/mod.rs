@@ -1,2 +1,53 @@
+pub mod cow_arc_slice;
+pub mod cow_arc_str;
+pub mod cow_rc_array;
 pub mod cow_rc_slice;
 pub mod cow_rc_str;
+
+/// `Borrow` requires the borrowed and owned forms to hash identically,
+/// otherwise `HashMap::get` silently misses
+#[cfg(test)]
+mod hash_consistency {
+	use super::{cow_rc_array::ToCowRcArray, cow_rc_slice::ToCowRcSlice, cow_rc_str::ToCowRcStr};
+	use crate::{rc::CowRc, sync::CowArc};
+	use std::{
+		collections::{hash_map::DefaultHasher, HashMap},
+		hash::{BuildHasher, BuildHasherDefault, Hash, RandomState},
+	};
+
+	fn assert_same_hashes<A: Hash + ?Sized, B: Hash + ?Sized>(a: &A, b: &B) {
+		let random = RandomState::new();
+		assert_eq!(random.hash_one(a), random.hash_one(b));
+		let default = BuildHasherDefault::<DefaultHasher>::default();
+		assert_eq!(default.hash_one(a), default.hash_one(b));
+	}
+
+	#[test]
+	fn str() {
+		for string in ["", "a", "Hello", "héllo wörld", "\0"] {
+			let rc: CowRc<str> = CowRc::from(string);
+			let arc: CowArc<str> = CowArc::from(string);
+			assert_same_hashes(&rc, string);
+			assert_same_hashes(ToCowRcStr::from_str(string), string);
+			assert_same_hashes(&arc, string);
+		}
+
+		let map: HashMap<CowRc<str>, i32> = HashMap::from([(CowRc::from("key"), 1)]);
+		assert_eq!(map.get("key"), Some(&1));
+	}
+
+	#[test]
+	fn slice() {
+		for slice in [&[][..], &[1], &[1, 2, 3], &[0, 0]] {
+			let rc: CowRc<[i32]> = CowRc::from(slice);
+			assert_same_hashes(&rc, slice);
+			assert_same_hashes(ToCowRcSlice::from_slice(slice), slice);
+		}
+
+		let array = [1, 2, 3];
+		assert_same_hashes(ToCowRcArray::from_array(&array), &array[..]);
+
+		let map: HashMap<CowRc<[u8]>, i32> = HashMap::from([(CowRc::from(&b"key"[..]), 1)]);
+		assert_eq!(map.get(&b"key"[..]), Some(&1));
+	}
+}
@@ -0,0 +1,521 @@
+use crate::{rc::CowRc, sync::CowArc};
+use std::{
+	ops::{Deref, Range},
+	slice::IterMut,
+	vec::Drain,
+};
+use sugaru::pipeline;
+
+/// Growable copy-on-write vector.
+///
+/// A `CowRc<[T]>` can't grow in place: each push reallocates the whole slice.
+/// A `CowRcVec<T>` wraps a `CowRc<Vec<T>>` instead, so pushes on a unique handle
+/// are amortized O(1) like a [`Vec`], the `Vec` being cloned only if shared
+#[derive(Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+#[allow(clippy::module_name_repetitions)]
+pub struct CowRcVec<T> {
+	vec: CowRc<Vec<T>>,
+}
+
+impl<T> CowRcVec<T> {
+	#[must_use]
+	pub fn new() -> Self {
+		pipeline!(Vec::new() => Self::from)
+	}
+
+	#[must_use]
+	pub fn with_capacity(capacity: usize) -> Self {
+		pipeline!(capacity |> Vec::with_capacity |> Self::from)
+	}
+
+	#[must_use]
+	pub fn len(&self) -> usize {
+		self.vec.len()
+	}
+
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.vec.is_empty()
+	}
+
+	#[must_use]
+	pub fn capacity(&self) -> usize {
+		self.vec.capacity()
+	}
+}
+
+impl<T: Clone> CowRcVec<T> {
+	/// Clones the inner `Vec` only if it is shared
+	fn make_mut(&mut self) -> &mut Vec<T> {
+		CowRc::make_mut(&mut self.vec)
+	}
+
+	pub fn push(&mut self, value: T) {
+		self.make_mut().push(value);
+	}
+
+	pub fn pop(&mut self) -> Option<T> {
+		self.make_mut().pop()
+	}
+
+	/// See [`Vec::reserve`], clones the `Vec` first if it is shared
+	pub fn reserve(&mut self, additional: usize) {
+		self.make_mut().reserve(additional);
+	}
+
+	/// See [`Vec::reserve_exact`], clones the `Vec` first if it is shared
+	pub fn reserve_exact(&mut self, additional: usize) {
+		self.make_mut().reserve_exact(additional);
+	}
+
+	/// See [`Vec::shrink_to_fit`], clones the `Vec` first if it is shared
+	/// (other owners keep their capacity)
+	pub fn shrink_to_fit(&mut self) {
+		self.make_mut().shrink_to_fit();
+	}
+
+	/// Applies `f` to every element, checking only once whether the `Vec` must be cloned
+	/// (indexing a `CowRc<[T]>` element by element would check at each write)
+	pub fn map_in_place(&mut self, f: impl FnMut(&mut T)) {
+		self.iter_mut().for_each(f);
+	}
+
+	/// Iterates mutably over the elements, cloning the `Vec` first if it is shared
+	/// (once, unlike indexing a `CowRc<[T]>` element by element)
+	pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+		self.make_mut().iter_mut()
+	}
+
+	/// Removes consecutive duplicates, see [`Vec::dedup`].
+	/// A shared `Vec` is only cloned if it does contain some
+	pub fn dedup(&mut self)
+	where
+		T: PartialEq,
+	{
+		if self.windows(2).any(|pair| pair[0] == pair[1]) {
+			self.make_mut().dedup();
+		}
+	}
+
+	/// Removes consecutive elements with the same key, see [`Vec::dedup_by_key`].
+	/// The `Vec` is cloned first if it is shared (`key` takes a mutable reference)
+	pub fn dedup_by_key<K: PartialEq>(&mut self, key: impl FnMut(&mut T) -> K) {
+		self.make_mut().dedup_by_key(key);
+	}
+
+	/// Removes the elements in `range`, returning them through an iterator (see [`Vec::drain`]).
+	/// The `Vec` is cloned first if it is shared, once for the whole range
+	///
+	/// # Panics
+	///
+	/// Panics if `range` is out of bounds
+	pub fn drain(&mut self, range: Range<usize>) -> Drain<'_, T> {
+		self.make_mut().drain(range)
+	}
+}
+
+impl<T> Clone for CowRcVec<T> {
+	/// Shares the inner `Vec`, without cloning it
+	fn clone(&self) -> Self {
+		Self {
+			vec: self.vec.clone(),
+		}
+	}
+}
+
+impl<T> Default for CowRcVec<T> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<T> Deref for CowRcVec<T> {
+	type Target = [T];
+
+	fn deref(&self) -> &Self::Target {
+		&self.vec
+	}
+}
+
+impl<T> From<Vec<T>> for CowRcVec<T> {
+	fn from(value: Vec<T>) -> Self {
+		Self {
+			vec: CowRc::new(value),
+		}
+	}
+}
+
+/// Thaws a shared slice, moving its elements if the `CowRc` is unique, cloning them otherwise.
+/// The `Vec` needs its own allocation: a `CowRc<[T]>` stores its counts next to the elements
+impl<T: Clone> From<CowRc<[T]>> for CowRcVec<T> {
+	fn from(value: CowRc<[T]>) -> Self {
+		pipeline!(value => CowRc::into_vec => Self::from)
+	}
+}
+
+/// Freezes the vector into a shared slice, moving the `Vec` out if it is unique.
+/// The elements are copied once into the slice allocation, sized exactly to the length
+impl<T: Clone> From<CowRcVec<T>> for CowRc<[T]> {
+	fn from(value: CowRcVec<T>) -> Self {
+		pipeline!(value.vec => CowRc::unwrap_or_clone => Self::from)
+	}
+}
+
+impl<T> FromIterator<T> for CowRcVec<T> {
+	fn from_iter<Iterator: IntoIterator<Item = T>>(iter: Iterator) -> Self {
+		pipeline!(iter |> Vec::from_iter |> Self::from)
+	}
+}
+
+impl<'a, T: Clone> IntoIterator for &'a mut CowRcVec<T> {
+	type Item = &'a mut T;
+	type IntoIter = IterMut<'a, T>;
+
+	/// See [`CowRcVec::iter_mut`]
+	fn into_iter(self) -> Self::IntoIter {
+		self.iter_mut()
+	}
+}
+
+impl<T: Clone> Extend<T> for CowRcVec<T> {
+	fn extend<Iterator: IntoIterator<Item = T>>(&mut self, iter: Iterator) {
+		self.make_mut().extend(iter);
+	}
+}
+
+/// Clones the elements, like [`Vec::extend_from_slice`]
+impl<'a, T: Clone + 'a> Extend<&'a T> for CowRcVec<T> {
+	fn extend<Iterator: IntoIterator<Item = &'a T>>(&mut self, iter: Iterator) {
+		self.make_mut().extend(iter.into_iter().cloned());
+	}
+}
+
+/// Thread-safe version of [`CowRcVec`], wrapping a `CowArc<Vec<T>>`.
+///
+/// It is [`Send`] and [`Sync`] when `T` is, so a snapshot can be handed off to other threads
+/// while the original keeps growing (cloning the `Vec` once on the first write after sharing)
+#[derive(Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+#[allow(clippy::module_name_repetitions)]
+pub struct CowArcVec<T> {
+	vec: CowArc<Vec<T>>,
+}
+
+impl<T> CowArcVec<T> {
+	#[must_use]
+	pub fn new() -> Self {
+		pipeline!(Vec::new() => Self::from)
+	}
+
+	#[must_use]
+	pub fn with_capacity(capacity: usize) -> Self {
+		pipeline!(capacity |> Vec::with_capacity |> Self::from)
+	}
+
+	#[must_use]
+	pub fn len(&self) -> usize {
+		self.vec.len()
+	}
+
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.vec.is_empty()
+	}
+
+	#[must_use]
+	pub fn capacity(&self) -> usize {
+		self.vec.capacity()
+	}
+}
+
+impl<T: Clone> CowArcVec<T> {
+	/// Clones the inner `Vec` only if it is shared
+	fn make_mut(&mut self) -> &mut Vec<T> {
+		CowArc::make_mut(&mut self.vec)
+	}
+
+	pub fn push(&mut self, value: T) {
+		self.make_mut().push(value);
+	}
+
+	pub fn pop(&mut self) -> Option<T> {
+		self.make_mut().pop()
+	}
+
+	/// See [`Vec::reserve`], clones the `Vec` first if it is shared
+	pub fn reserve(&mut self, additional: usize) {
+		self.make_mut().reserve(additional);
+	}
+
+	/// See [`Vec::reserve_exact`], clones the `Vec` first if it is shared
+	pub fn reserve_exact(&mut self, additional: usize) {
+		self.make_mut().reserve_exact(additional);
+	}
+
+	/// See [`Vec::shrink_to_fit`], clones the `Vec` first if it is shared
+	/// (other owners keep their capacity)
+	pub fn shrink_to_fit(&mut self) {
+		self.make_mut().shrink_to_fit();
+	}
+}
+
+impl<T> Clone for CowArcVec<T> {
+	/// Shares the inner `Vec`, without cloning it
+	fn clone(&self) -> Self {
+		Self {
+			vec: self.vec.clone(),
+		}
+	}
+}
+
+impl<T> Default for CowArcVec<T> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<T> Deref for CowArcVec<T> {
+	type Target = [T];
+
+	fn deref(&self) -> &Self::Target {
+		&self.vec
+	}
+}
+
+impl<T> From<Vec<T>> for CowArcVec<T> {
+	fn from(value: Vec<T>) -> Self {
+		Self {
+			vec: CowArc::new(value),
+		}
+	}
+}
+
+/// Thaws a shared slice, moving its elements if the `CowArc` is unique, cloning them otherwise.
+/// The `Vec` needs its own allocation: a `CowArc<[T]>` stores its counts next to the elements
+impl<T: Clone> From<CowArc<[T]>> for CowArcVec<T> {
+	fn from(value: CowArc<[T]>) -> Self {
+		pipeline!(value => CowArc::into_vec => Self::from)
+	}
+}
+
+/// Freezes the vector into a shared slice, moving the `Vec` out if it is unique.
+/// The elements are copied once into the slice allocation, sized exactly to the length
+impl<T: Clone> From<CowArcVec<T>> for CowArc<[T]> {
+	fn from(value: CowArcVec<T>) -> Self {
+		pipeline!(value.vec => CowArc::unwrap_or_clone => Self::from)
+	}
+}
+
+impl<T> FromIterator<T> for CowArcVec<T> {
+	fn from_iter<Iterator: IntoIterator<Item = T>>(iter: Iterator) -> Self {
+		pipeline!(iter |> Vec::from_iter |> Self::from)
+	}
+}
+
+impl<T: Clone> Extend<T> for CowArcVec<T> {
+	fn extend<Iterator: IntoIterator<Item = T>>(&mut self, iter: Iterator) {
+		self.make_mut().extend(iter);
+	}
+}
+
+/// Clones the elements, like [`Vec::extend_from_slice`]
+impl<'a, T: Clone + 'a> Extend<&'a T> for CowArcVec<T> {
+	fn extend<Iterator: IntoIterator<Item = &'a T>>(&mut self, iter: Iterator) {
+		self.make_mut().extend(iter.into_iter().cloned());
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::cell::Cell;
+
+	thread_local! {
+		static CLONES: Cell<usize> = const { Cell::new(0) };
+	}
+
+	/// Counts its clones in `CLONES`
+	#[derive(Debug, PartialEq)]
+	struct Counted(i32);
+
+	impl Clone for Counted {
+		fn clone(&self) -> Self {
+			CLONES.set(CLONES.get() + 1);
+			Self(self.0)
+		}
+	}
+
+	#[test]
+	fn amortized_push() {
+		let mut vec = CowRcVec::new();
+		let mut reallocations = 0;
+		let mut ptr = vec.as_ptr();
+		for i in 0..1000 {
+			vec.push(i);
+			if vec.as_ptr() != ptr {
+				reallocations += 1;
+				ptr = vec.as_ptr();
+			}
+		}
+		assert_eq!(vec.len(), 1000);
+		assert!(reallocations <= 11); // Capacity doubling, like a Vec
+	}
+
+	#[test]
+	fn copy_on_write() {
+		let mut vec: CowRcVec<i32> = [1, 2].into_iter().collect();
+		let original = vec.clone();
+		vec.push(3);
+		vec.extend([4, 5]);
+		assert_eq!(vec.pop(), Some(5));
+
+		assert_eq!(&*vec, [1, 2, 3, 4]);
+		assert_eq!(&*original, [1, 2]);
+		assert!(!original.is_empty());
+	}
+
+	#[test]
+	fn concurrent_append() {
+		let mut log: CowArcVec<String> = CowArcVec::new();
+		log.push("start".to_owned());
+
+		let snapshot = log.clone();
+		let reader = std::thread::spawn(move || snapshot.join(","));
+		log.push("appended".to_owned());
+		assert_eq!(reader.join().unwrap(), "start");
+
+		let snapshots: Vec<_> = (0..4)
+			.map(|_| {
+				let snapshot = log.clone();
+				std::thread::spawn(move || snapshot.len())
+			})
+			.collect();
+		log.extend(["appended again".to_owned()]);
+		for snapshot in snapshots {
+			assert_eq!(snapshot.join().unwrap(), 2);
+		}
+		assert_eq!(log.len(), 3);
+	}
+
+	#[test]
+	fn map_in_place() {
+		let mut vec: CowRcVec<Counted> = (0..10).map(Counted).collect();
+		let original = vec.clone();
+		CLONES.set(0);
+
+		vec.map_in_place(|element| element.0 *= 2);
+		assert_eq!(CLONES.get(), 10); // The Vec was cloned once
+		vec.map_in_place(|element| element.0 += 1);
+		assert_eq!(CLONES.get(), 10); // Now unique, no more clone
+
+		assert_eq!(vec[9], Counted(19));
+		assert_eq!(original[9], Counted(9));
+	}
+
+	#[test]
+	fn capacity() {
+		let mut vec: CowRcVec<i32> = CowRcVec::with_capacity(10);
+		assert!(vec.capacity() >= 10);
+		vec.extend([1, 2]);
+		vec.reserve(20);
+		assert!(vec.capacity() >= 22);
+		vec.reserve_exact(30);
+		assert!(vec.capacity() >= 32);
+
+		let shared = vec.clone();
+		vec.shrink_to_fit();
+		assert!(vec.capacity() < 32);
+		assert!(shared.capacity() >= 32); // The other owner is unaffected
+
+		let mut arc_vec: CowArcVec<i32> = CowArcVec::with_capacity(10);
+		let arc_shared = arc_vec.clone();
+		arc_vec.shrink_to_fit();
+		assert_eq!(arc_vec.capacity(), 0);
+		assert!(arc_shared.capacity() >= 10);
+	}
+
+	#[test]
+	fn slice_conversions() {
+		let mut vec: CowRcVec<Counted> = (0..3).map(Counted).collect();
+		vec.push(Counted(3));
+		CLONES.set(0);
+
+		let frozen: CowRc<[Counted]> = CowRc::from(vec);
+		assert_eq!(CLONES.get(), 0); // Unique, moved
+		assert_eq!(frozen[3], Counted(3));
+
+		let shared = frozen.clone();
+		let mut thawed = CowRcVec::from(frozen);
+		assert_eq!(CLONES.get(), 4); // Shared, cloned
+		thawed.push(Counted(4));
+		assert_eq!(thawed.len(), 5);
+		assert_eq!(shared.len(), 4);
+
+		let arc_frozen: CowArc<[i32]> = CowArc::from(CowArcVec::from(vec![1, 2]));
+		assert_eq!(&*CowArcVec::from(arc_frozen), [1, 2]);
+	}
+
+	#[test]
+	fn drain() {
+		let mut vec: CowRcVec<Counted> = (0..5).map(Counted).collect();
+		let original = vec.clone();
+		CLONES.set(0);
+
+		let drained: Vec<_> = vec.drain(1..3).collect();
+		assert_eq!(drained, [Counted(1), Counted(2)]);
+		assert_eq!(&*vec, [Counted(0), Counted(3), Counted(4)]);
+		assert_eq!(CLONES.get(), 5); // The Vec was cloned once
+		assert_eq!(original.len(), 5);
+
+		assert_eq!(vec.drain(0..1).next(), Some(Counted(0)));
+		assert_eq!(CLONES.get(), 5); // Now unique
+	}
+
+	#[test]
+	fn iter_mut() {
+		let mut vec: CowRcVec<Counted> = (0..10).map(Counted).collect();
+		let original = vec.clone();
+		CLONES.set(0);
+
+		for element in &mut vec {
+			element.0 = -element.0;
+		}
+		assert_eq!(CLONES.get(), 10); // The Vec was cloned once
+		vec.iter_mut().for_each(|element| element.0 -= 1);
+		assert_eq!(CLONES.get(), 10);
+
+		assert_eq!(vec[9], Counted(-10));
+		assert_eq!(original[9], Counted(9));
+	}
+
+	#[test]
+	fn extend_from_references() {
+		let mut vec: CowRcVec<String> = CowRcVec::from(vec!["a".to_owned()]);
+		let original = vec.clone();
+		let more = ["b".to_owned(), "c".to_owned()];
+		vec.extend(&more);
+		vec.extend(more[..1].iter());
+		assert_eq!(&*vec, ["a", "b", "c", "b"]);
+		assert_eq!(&*original, ["a"]);
+
+		let mut arc_vec: CowArcVec<i32> = CowArcVec::new();
+		arc_vec.extend(&[1, 2][..]);
+		assert_eq!(&*arc_vec, [1, 2]);
+	}
+
+	#[test]
+	fn dedup() {
+		let mut log: CowRcVec<i32> = CowRcVec::from(vec![1, 1, 2, 3, 3, 3, 1]);
+		let original = log.clone();
+		log.dedup();
+		assert_eq!(&*log, [1, 2, 3, 1]);
+		assert_eq!(original.len(), 7);
+
+		let mut unique = log.clone();
+		unique.dedup(); // Nothing to remove: still shared
+		assert_eq!(unique.as_ptr(), log.as_ptr());
+
+		unique.dedup_by_key(|value| *value / 2);
+		assert_eq!(&*unique, [1, 2, 1]);
+		assert_eq!(&*log, [1, 2, 3, 1]);
+	}
+}